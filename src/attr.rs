@@ -0,0 +1,323 @@
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    parenthesized, Attribute, Expr, Generics, Ident, Lit, LitStr, Path, Token, Type, WherePredicate,
+};
+
+/// Keys for the derive-specific sections of a container attribute,
+/// e.g. `#[force(clone(bound = "U: Clone"))]`.
+const DERIVES: &[&str] = &[
+    "default",
+    "clone",
+    "copy",
+    "partial_eq",
+    "eq",
+    "hash",
+    "partial_ord",
+    "ord",
+    "debug",
+    "display",
+    "deref",
+    "deref_mut",
+    "from",
+    "into",
+    "zeroed",
+    "add",
+    "sub",
+    "mul",
+    "div",
+    "add_assign",
+    "sub_assign",
+    "mul_assign",
+    "div_assign",
+    "neg",
+    "not",
+    "sum",
+    "product",
+];
+
+/// Derives that compare or hash fields, and so can be told to skip them,
+/// e.g. `#[force(skip(hash))]`.
+const SKIPPABLE: &[&str] = &["partial_eq", "hash", "partial_ord", "ord"];
+
+/// A single `name`, `name = value` or `name(...)` entry inside `#[force(...)]`.
+pub(crate) struct Arg {
+    pub name: Ident,
+    pub value: ArgValue,
+}
+
+pub(crate) enum ArgValue {
+    Flag,
+    Expr(Box<Expr>),
+    List(Vec<Arg>),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.call(Ident::parse_any)?;
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            ArgValue::Expr(Box::new(input.parse()?))
+        } else if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            ArgValue::List(parse_args(&content)?)
+        } else {
+            ArgValue::Flag
+        };
+
+        Ok(Arg { name, value })
+    }
+}
+
+impl Arg {
+    pub fn lit_str(&self) -> syn::Result<&LitStr> {
+        match &self.value {
+            ArgValue::Expr(expr) => match &**expr {
+                Expr::Lit(expr) => match &expr.lit {
+                    Lit::Str(lit) => Ok(lit),
+                    lit => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+                },
+                expr => Err(syn::Error::new_spanned(expr, "expected a string literal")),
+            },
+            _ => Err(syn::Error::new_spanned(
+                &self.name,
+                format!("expected `{} = \"...\"`", self.name),
+            )),
+        }
+    }
+
+    pub fn unknown(&self) -> syn::Error {
+        syn::Error::new_spanned(
+            &self.name,
+            format!("unknown force attribute `{}`", self.name),
+        )
+    }
+}
+
+fn parse_args(input: ParseStream) -> syn::Result<Vec<Arg>> {
+    let args = Punctuated::<Arg, Token![,]>::parse_terminated(input)?;
+    Ok(args.into_iter().collect())
+}
+
+/// Collects the entries of every `#[force(...)]` attribute in `attrs`.
+pub(crate) fn force_args(attrs: &[Attribute]) -> syn::Result<Vec<Arg>> {
+    let mut args = vec![];
+
+    for attr in attrs.iter().filter(|a| a.path.is_ident("force")) {
+        args.extend(attr.parse_args_with(parse_args)?);
+    }
+
+    Ok(args)
+}
+
+/// Attributes applied to the struct or enum itself.
+#[derive(Default)]
+pub(crate) struct ContainerAttrs {
+    /// Extra where-clause predicates, tagged with the derive they are restricted to.
+    bounds: Vec<(Option<String>, WherePredicate)>,
+    /// Requires `Hash` and the ordering traits to use the same fields as `PartialEq`.
+    pub check_consistency: bool,
+    /// The `Display` format string, e.g. `#[force(display = "{0} kg")]`.
+    pub display: Option<LitStr>,
+    /// Extra `Mul` and `Div` impls, e.g. `#[force(mul(rhs = "Length<U>", output = "Area<U>"))]`.
+    pub operators: Vec<Operator>,
+}
+
+/// An operator impl with a right-hand side or output other than `Self`.
+pub(crate) struct Operator {
+    pub derive: String,
+    pub rhs: Option<Type>,
+    pub output: Option<Type>,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut container = ContainerAttrs::default();
+
+        for arg in force_args(attrs)? {
+            let name = arg.name.to_string();
+
+            match &arg.value {
+                _ if name == "bound" => container.push_bounds(None, &arg)?,
+                ArgValue::Flag if name == "check_consistency" => container.check_consistency = true,
+                ArgValue::Expr(_) if name == "display" => {
+                    container.display = Some(arg.lit_str()?.clone())
+                }
+                ArgValue::List(args) if DERIVES.contains(&name.as_str()) => {
+                    let mut operator = Operator {
+                        derive: name.clone(),
+                        rhs: None,
+                        output: None,
+                    };
+
+                    for arg in args {
+                        let is_operator = name == "mul" || name == "div";
+
+                        if arg.name == "bound" {
+                            container.push_bounds(Some(name.clone()), arg)?;
+                        } else if arg.name == "rhs" && is_operator {
+                            operator.rhs = Some(arg.lit_str()?.parse()?);
+                        } else if arg.name == "output" && is_operator {
+                            operator.output = Some(arg.lit_str()?.parse()?);
+                        } else {
+                            return Err(arg.unknown());
+                        }
+                    }
+
+                    if operator.rhs.is_some() || operator.output.is_some() {
+                        container.operators.push(operator);
+                    }
+                }
+                _ => return Err(arg.unknown()),
+            }
+        }
+
+        Ok(container)
+    }
+
+    fn push_bounds(&mut self, derive: Option<String>, arg: &Arg) -> syn::Result<()> {
+        let predicates = arg
+            .lit_str()?
+            .parse_with(Punctuated::<WherePredicate, Token![,]>::parse_terminated)?;
+
+        self.bounds
+            .extend(predicates.into_iter().map(|p| (derive.clone(), p)));
+
+        Ok(())
+    }
+
+    /// Returns `generics` with the bounds that apply to `derive` appended to its where-clause.
+    pub fn generics(&self, generics: &Generics, derive: &str) -> Generics {
+        let mut generics = generics.clone();
+
+        let predicates = self
+            .bounds
+            .iter()
+            .filter(|(d, _)| d.is_none() || d.as_deref() == Some(derive))
+            .map(|(_, p)| p.clone());
+
+        generics.make_where_clause().predicates.extend(predicates);
+
+        generics
+    }
+}
+
+/// Attributes applied to a field of a struct or enum variant.
+#[derive(Default)]
+pub(crate) struct FieldAttrs {
+    /// The derives that ignore the field: all of `PartialEq`, `Hash` and the ordering traits
+    /// for `#[force(skip)]`, or those listed in `#[force(skip(...))]`.
+    skip: Vec<String>,
+    /// The value used by `Default` in place of `Default::default()`.
+    pub default: Option<Expr>,
+    /// A function `fn(&T, &T) -> bool` used by `PartialEq` in place of `PartialEq::eq`.
+    pub eq_with: Option<Path>,
+    /// A function `fn(&T, &mut H)` used by `Hash` in place of `Hash::hash`.
+    pub hash_with: Option<Path>,
+    /// Marks the field targeted by `Deref` and `DerefMut`.
+    pub deref: bool,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut field = FieldAttrs::default();
+
+        for arg in force_args(attrs)? {
+            match &arg.value {
+                ArgValue::Flag if arg.name == "skip" => {
+                    field.skip = SKIPPABLE.iter().map(|d| d.to_string()).collect()
+                }
+                ArgValue::List(args) if arg.name == "skip" => {
+                    for arg in args {
+                        let derive = arg.name.to_string();
+
+                        match arg.value {
+                            ArgValue::Flag if SKIPPABLE.contains(&derive.as_str()) => {
+                                field.skip.push(derive)
+                            }
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    &arg.name,
+                                    format!("fields cannot be skipped by `{}`", derive),
+                                ))
+                            }
+                        }
+                    }
+                }
+                ArgValue::Flag if arg.name == "default" => field.default = None,
+                ArgValue::Flag if arg.name == "deref" => field.deref = true,
+                ArgValue::Expr(expr) if arg.name == "default" => {
+                    field.default = Some((**expr).clone())
+                }
+                _ if arg.name == "eq_with" => field.eq_with = Some(arg.lit_str()?.parse()?),
+                _ if arg.name == "hash_with" => field.hash_with = Some(arg.lit_str()?.parse()?),
+                _ => return Err(arg.unknown()),
+            }
+        }
+
+        Ok(field)
+    }
+
+    /// Returns `true` if the field is ignored by `derive`.
+    pub fn skips(&self, derive: &str) -> bool {
+        self.skip.iter().any(|d| d == derive)
+    }
+
+    /// Returns the function given to `derive` by `eq_with` or `hash_with`, if any.
+    pub fn with(&self, derive: &str) -> Option<&Path> {
+        match derive {
+            "partial_eq" => self.eq_with.as_ref(),
+            "hash" => self.hash_with.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Attributes applied to an enum variant.
+#[derive(Default)]
+pub(crate) struct VariantAttrs {
+    /// Marks the variant returned by `Default`, via `#[default]` or `#[force(default)]`.
+    pub default: bool,
+    /// The `Display` format string for the variant.
+    pub display: Option<LitStr>,
+    /// Excludes the variant from `From`, via `#[force(skip(from))]`.
+    pub skip_from: bool,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut variant = VariantAttrs {
+            default: attrs.iter().any(|a| a.path.is_ident("default")),
+            display: None,
+            skip_from: false,
+        };
+
+        for arg in force_args(attrs)? {
+            match &arg.value {
+                ArgValue::Flag if arg.name == "default" => variant.default = true,
+                ArgValue::Expr(_) if arg.name == "display" => {
+                    variant.display = Some(arg.lit_str()?.clone())
+                }
+                ArgValue::List(args) if arg.name == "skip" => {
+                    for arg in args {
+                        match arg.value {
+                            ArgValue::Flag if arg.name == "from" => variant.skip_from = true,
+                            _ => {
+                                return Err(syn::Error::new_spanned(
+                                    &arg.name,
+                                    format!("variants cannot be skipped by `{}`", arg.name),
+                                ))
+                            }
+                        }
+                    }
+                }
+                _ => return Err(arg.unknown()),
+            }
+        }
+
+        Ok(variant)
+    }
+}
@@ -1,10 +1,18 @@
 extern crate proc_macro;
 
+mod attr;
+
+use attr::{ContainerAttrs, FieldAttrs, Operator, VariantAttrs};
 use proc_macro::TokenStream;
-use quote::quote;
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Fields, Ident, Index, Item, ItemEnum, ItemStruct, Token};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Field, Fields, Generics, Ident, Index, Item,
+    ItemEnum, ItemStruct, ItemUnion, LitStr, Member, Token, Type,
+};
 
 fn get_field_identifiers(fields: &Fields) -> Vec<Ident> {
     match fields {
@@ -24,307 +32,665 @@ fn get_field_identifiers(fields: &Fields) -> Vec<Ident> {
     }
 }
 
-#[proc_macro_derive(ForceDefault)]
+fn binding(field: &Ident, suffix: &str) -> Ident {
+    Ident::new(&format!("{}{}", field.unraw(), suffix), field.span())
+}
+
+/// Returns the marker type named by `ty`, `PhantomData` or `PhantomPinned`, recognised by the last
+/// path segment. Such fields carry no data: derives construct them as literals and never compare
+/// or hash them.
+fn phantom_marker(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty
+            .path
+            .segments
+            .last()
+            .map(|s| &s.ident)
+            .filter(|i| *i == "PhantomData" || *i == "PhantomPinned"),
+        _ => None,
+    }
+}
+
+fn is_phantom(ty: &Type) -> bool {
+    phantom_marker(ty).is_some()
+}
+
+/// Returns the name of the primitive type named by `ty`, written either bare or through a
+/// `primitive` module such as `core::primitive`.
+fn primitive(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let segments = &path.path.segments;
+            let qualified = match segments.len() {
+                1 => true,
+                n => segments[n - 2].ident == "primitive",
+            };
+
+            segments
+                .last()
+                .filter(|s| qualified && s.arguments.is_empty())
+                .map(|s| &s.ident)
+                .filter(|i| {
+                    matches!(
+                        i.to_string().as_str(),
+                        "u8" | "u16"
+                            | "u32"
+                            | "u64"
+                            | "u128"
+                            | "usize"
+                            | "i8"
+                            | "i16"
+                            | "i32"
+                            | "i64"
+                            | "i128"
+                            | "isize"
+                            | "f32"
+                            | "f64"
+                            | "bool"
+                            | "char"
+                    )
+                })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the `::core::marker` literal for a `PhantomData` or `PhantomPinned` field.
+fn phantom_value(ty: &Type) -> Option<proc_macro2::TokenStream> {
+    phantom_marker(ty).map(|marker| quote! { ::core::marker::#marker })
+}
+
+fn default_values(fields: &Fields) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    fields
+        .iter()
+        .map(|f| match FieldAttrs::parse(&f.attrs)?.default {
+            Some(expr) => Ok(quote! { #expr }),
+            None => Ok(phantom_value(&f.ty)
+                .unwrap_or_else(|| quote! { ::core::default::Default::default() })),
+        })
+        .collect()
+}
+
+/// Returns `true` if `derive` ignores `field`, either as asked by `#[force(skip)]` or because it
+/// is a phantom marker.
+fn is_skipped(field: &Field, attrs: &FieldAttrs, derive: &str) -> bool {
+    attrs.skips(derive) || is_phantom(&field.ty)
+}
+
+fn skipped_fields(fields: &Fields, derive: &str) -> syn::Result<Vec<bool>> {
+    fields
+        .iter()
+        .map(|f| Ok(is_skipped(f, &FieldAttrs::parse(&f.attrs)?, derive)))
+        .collect()
+}
+
+/// Returns the function `derive` calls on each field it does not skip: the path given by
+/// `eq_with` or `hash_with`, or `default`.
+fn field_fns(
+    fields: &Fields,
+    derive: &str,
+    default: proc_macro2::TokenStream,
+) -> syn::Result<Vec<proc_macro2::TokenStream>> {
+    let mut fns = vec![];
+
+    for field in fields {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+
+        if !is_skipped(field, &attrs, derive) {
+            fns.push(match attrs.with(derive) {
+                Some(path) => quote! { #path },
+                None => default.clone(),
+            });
+        }
+    }
+
+    Ok(fns)
+}
+
+/// Builds a pattern for each field of a variant, binding it to its identifier with `suffix`
+/// appended, or to `_` if `derive` skips the field. Returns the patterns and the bound identifiers.
+fn variant_bindings(
+    fields: &Fields,
+    suffix: &str,
+    derive: &str,
+) -> syn::Result<(Vec<proc_macro2::TokenStream>, Vec<Ident>)> {
+    let mut patterns = vec![];
+    let mut bindings = vec![];
+
+    for (field, skip) in get_field_identifiers(fields)
+        .into_iter()
+        .zip(skipped_fields(fields, derive)?)
+    {
+        if skip {
+            patterns.push(quote! { _ });
+        } else {
+            let ident = binding(&field, suffix);
+            patterns.push(quote! { #ident });
+            bindings.push(ident);
+        }
+    }
+
+    Ok((patterns, bindings))
+}
+
+/// With `#[force(check_consistency)]`, requires `derive` to use the same fields as `PartialEq`,
+/// so that values comparing equal also hash and order equally. For `Hash`, a field compared with
+/// `eq_with` must also be hashed with `hash_with`, and vice versa. There is no ordering
+/// counterpart to `eq_with`, so `PartialOrd` and `Ord` reject fields that use it.
+fn check_consistency<'a>(
+    attrs: &ContainerAttrs,
+    fields: impl IntoIterator<Item = &'a Field>,
+    derive: &str,
+    name: &str,
+) -> syn::Result<()> {
+    if !attrs.check_consistency {
+        return Ok(());
+    }
+
+    let mut errors: Option<syn::Error> = None;
+
+    for field in fields {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+
+        let message = match (
+            is_skipped(field, &attrs, "partial_eq"),
+            is_skipped(field, &attrs, derive),
+        ) {
+            (true, false) => format!("field is used by {} but skipped by PartialEq", name),
+            (false, true) => format!("field is used by PartialEq but skipped by {}", name),
+            (false, false) if derive == "hash" => match (&attrs.eq_with, &attrs.hash_with) {
+                (Some(_), None) => {
+                    "field is compared with `eq_with` but hashed without `hash_with`".to_string()
+                }
+                (None, Some(_)) => {
+                    "field is hashed with `hash_with` but compared without `eq_with`".to_string()
+                }
+                _ => continue,
+            },
+            (false, false) if attrs.eq_with.is_some() && derive != "hash" => format!(
+                "field is compared with `eq_with`, which {} cannot use to order it",
+                name
+            ),
+            _ => continue,
+        };
+
+        let error = syn::Error::new_spanned(field, message);
+        match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        }
+    }
+
+    errors.map_or(Ok(()), Err)
+}
+
+/// Joins `terms` with `&&`, or returns `true` if there are none.
+fn conjunction(terms: impl Iterator<Item = proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
+    let terms = terms.collect::<Punctuated<_, Token![&&]>>();
+
+    if terms.is_empty() {
+        quote! { true }
+    } else {
+        quote! { #terms }
+    }
+}
+
+/// Parses every field's `#[force(...)]` attributes, so that unknown keys are reported by all
+/// derives rather than only those that read field attributes.
+fn check_field_attrs<'a>(fields: impl IntoIterator<Item = &'a Field>) -> syn::Result<()> {
+    for field in fields {
+        FieldAttrs::parse(&field.attrs)?;
+    }
+
+    Ok(())
+}
+
+/// Parses every variant's `#[force(...)]` attributes, so that unknown keys are reported by all
+/// enum derives rather than only those that read variant attributes.
+fn check_variant_attrs(item_enum: &ItemEnum) -> syn::Result<()> {
+    for v in &item_enum.variants {
+        VariantAttrs::parse(&v.attrs)?;
+    }
+
+    Ok(())
+}
+
+fn unsupported_item(item: &Item, derive: &str) -> syn::Error {
+    match item {
+        Item::Union(item_union) => syn::Error::new(
+            item_union.union_token.span,
+            format!(
+                "{} cannot be implemented for unions; only ForceClone and ForceCopy support them",
+                derive
+            ),
+        ),
+        _ => syn::Error::new_spanned(
+            item,
+            format!("{} can only be implemented for enums and structs", derive),
+        ),
+    }
+}
+
+#[proc_macro_derive(ForceDefault, attributes(force, default))]
 pub fn force_default(input: TokenStream) -> TokenStream {
-    let ast: Item = syn::parse(input).unwrap();
+    let item = parse_macro_input!(input as Item);
 
-    let tokens = match &ast {
+    derive_default(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_default(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
         Item::Enum(item_enum) => impl_default_enum(item_enum),
         Item::Struct(item_struct) => impl_default_struct(item_struct),
-        _ => panic!("ForceDefault can only be implemented for enums and structs."),
-    };
-
-    tokens.into()
+        _ => Err(unsupported_item(item, "ForceDefault")),
+    }
 }
 
-fn impl_default_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+fn impl_default_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    let generics = attrs.generics(&item_enum.generics, "default");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_enum.ident;
 
-    let first_variant = item_enum
-        .variants
-        .first()
-        .unwrap_or_else(|| panic!("{} must have variants to implement Default", ty));
+    let mut default_variant = None;
+
+    for v in &item_enum.variants {
+        if VariantAttrs::parse(&v.attrs)?.default {
+            if default_variant.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &v.ident,
+                    "multiple variants are marked as the default",
+                ));
+            }
+
+            default_variant = Some(v);
+        }
+    }
 
-    let variant = &first_variant.ident;
+    let default_variant = default_variant
+        .or_else(|| item_enum.variants.first())
+        .ok_or_else(|| {
+            syn::Error::new(
+                item_enum.brace_token.span,
+                format!("{} must have variants to implement Default", ty),
+            )
+        })?;
 
-    match &first_variant.fields {
+    let variant = &default_variant.ident;
+    let defaults = default_values(&default_variant.fields)?;
+
+    match &default_variant.fields {
         Fields::Named(fields) => {
             let fields = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics Default for #ty #ty_generics #where_clause {
+                impl #impl_generics ::core::default::Default for #ty #ty_generics #where_clause {
                     #[inline]
                     fn default() -> #ty #ty_generics {
                         #ty :: #variant {
-                            #( #fields: Default::default(), )*
+                            #( #fields: #defaults, )*
                         }
                     }
                 }
-            }
+            })
         }
-        Fields::Unnamed(fields) => {
-            let fields = fields
-                .unnamed
-                .iter()
-                .map(|_| quote! { Default::default() })
-                .collect::<Punctuated<_, Token![,]>>();
+        Fields::Unnamed(_) => {
+            let fields = defaults.into_iter().collect::<Punctuated<_, Token![,]>>();
 
             let fields = fields.pairs();
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics Default for #ty #ty_generics #where_clause {
+                impl #impl_generics ::core::default::Default for #ty #ty_generics #where_clause {
                     #[inline]
                     fn default() -> #ty #ty_generics {
                         #ty :: #variant( #( #fields )* )
                     }
                 }
-            }
+            })
         }
-        Fields::Unit => {
-            quote! {
-                #[automatically_derived]
-                impl #impl_generics Default for #ty #ty_generics #where_clause {
-                    #[inline]
-                    fn default() -> #ty #ty_generics {
-                        #ty :: #variant
-                    }
+        Fields::Unit => Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::default::Default for #ty #ty_generics #where_clause {
+                #[inline]
+                fn default() -> #ty #ty_generics {
+                    #ty :: #variant
                 }
             }
-        }
+        }),
     }
 }
 
-fn impl_default_struct(item_struct: &ItemStruct) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+fn impl_default_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "default");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_struct.ident;
 
+    let defaults = default_values(&item_struct.fields)?;
+
     match &item_struct.fields {
         Fields::Named(fields) => {
             let fields = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics Default for #ty #ty_generics #where_clause {
+                impl #impl_generics ::core::default::Default for #ty #ty_generics #where_clause {
                     #[inline]
                     fn default() -> #ty #ty_generics {
                         #ty {
-                            #( #fields: Default::default(), )*
+                            #( #fields: #defaults, )*
                         }
                     }
                 }
-            }
+            })
         }
-        Fields::Unnamed(fields) => {
-            let default = fields
-                .unnamed
-                .iter()
-                .map(|_| quote! { Default::default() })
-                .collect::<Punctuated<_, Token![,]>>();
+        Fields::Unnamed(_) => {
+            let default = defaults.into_iter().collect::<Punctuated<_, Token![,]>>();
 
             let default = default.pairs();
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics Default for #ty #ty_generics #where_clause {
+                impl #impl_generics ::core::default::Default for #ty #ty_generics #where_clause {
                     #[inline]
                     fn default() -> #ty #ty_generics {
                         #ty ( #( #default )* )
                     }
                 }
-            }
+            })
         }
-        Fields::Unit => {
-            quote! {
-                #[automatically_derived]
-                impl #impl_generics Default for #ty #ty_generics #where_clause {
-                    #[inline]
-                    fn default() -> Self {
-                        Self
-                    }
+        Fields::Unit => Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::default::Default for #ty #ty_generics #where_clause {
+                #[inline]
+                fn default() -> Self {
+                    Self
                 }
             }
-        }
+        }),
     }
 }
 
-#[proc_macro_derive(ForceClone)]
+#[proc_macro_derive(ForceClone, attributes(force))]
 pub fn force_clone(input: TokenStream) -> TokenStream {
-    let ast: Item = syn::parse(input).unwrap();
+    let item = parse_macro_input!(input as Item);
 
-    let tokens = match &ast {
+    derive_clone(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_clone(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
         Item::Enum(item_enum) => impl_clone_enum(item_enum),
         Item::Struct(item_struct) => impl_clone_struct(item_struct),
-        _ => panic!("ForceClone can only be implemented for enums and structs."),
-    };
-
-    tokens.into()
+        Item::Union(item_union) => impl_clone_union(item_union),
+        _ => Err(unsupported_item(item, "ForceClone")),
+    }
 }
 
-fn impl_clone_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+fn impl_clone_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "clone");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_enum.ident;
 
-    let variants = item_enum.variants.iter().map(|v| {
-        let variant = &v.ident;
+    if item_enum.variants.is_empty() {
+        return Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::clone::Clone for #ty #ty_generics #where_clause {
+                #[inline]
+                fn clone(&self) -> Self {
+                    match *self {}
+                }
+            }
+        });
+    }
 
-        let fields = get_field_identifiers(&v.fields);
-        let fields1 = fields.iter();
-        let fields = fields.iter();
+    let variants = item_enum
+        .variants
+        .iter()
+        .map(|v| {
+            let variant = &v.ident;
+            let members = field_members(&v.fields);
+
+            let (patterns, _) = variant_bindings(&v.fields, "_value", "clone")?;
+            let values =
+                v.fields
+                    .iter()
+                    .zip(get_field_identifiers(&v.fields))
+                    .map(|(field, ident)| match phantom_value(&field.ty) {
+                        Some(value) => value,
+                        None => {
+                            let ident = binding(&ident, "_value");
+                            quote! { ::core::clone::Clone::clone(#ident) }
+                        }
+                    });
 
-        match &v.fields {
-            Fields::Named(_) => {
-                quote! {
-                    Self::#variant { #( #fields, )* } => {
-                        Self::#variant { #( #fields1: #fields1.clone() )* }
+            Ok(match &v.fields {
+                Fields::Unit => quote! {
+                    Self::#variant => Self::#variant,
+                },
+                _ => quote! {
+                    Self::#variant { #( #members: #patterns, )* } => {
+                        Self::#variant { #( #members: #values, )* }
                     }
-                }
-            }
-            Fields::Unnamed(_) => {
-                quote! {
-                    Self::#variant (
-                        #( #fields, )*
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let variants_from = item_enum
+        .variants
+        .iter()
+        .map(|v| {
+            let variant = &v.ident;
+            let members = field_members(&v.fields);
+
+            let (patterns_lhs, fields_lhs) = variant_bindings(&v.fields, "_lhs", "clone")?;
+            let (patterns_rhs, fields_rhs) = variant_bindings(&v.fields, "_rhs", "clone")?;
+
+            Ok(match &v.fields {
+                Fields::Unit => quote! {
+                    (Self::#variant, Self::#variant) => {}
+                },
+                _ => quote! {
+                    (
+                        Self::#variant { #( #members: #patterns_lhs, )* },
+                        Self::#variant { #( #members: #patterns_rhs, )* },
                     ) => {
-                        Self::#variant ( #( #fields1.clone(), )* )
+                        #( ::core::clone::Clone::clone_from(#fields_lhs, #fields_rhs); )*
                     }
-                }
-            }
-            Fields::Unit => {
-                quote! {
-                    Self::#variant => Self::#variant,
-                }
-            }
-        }
-    });
+                },
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics Clone for #ty #ty_generics #where_clause {
+        impl #impl_generics ::core::clone::Clone for #ty #ty_generics #where_clause {
             #[inline]
             fn clone(&self) -> Self {
                 match self {
                     #( #variants )*
                 }
             }
+
+            #[inline]
+            fn clone_from(&mut self, other: &Self) {
+                match (self, other) {
+                    #( #variants_from )*
+                    (lhs, rhs) => *lhs = ::core::clone::Clone::clone(rhs),
+                }
+            }
         }
-    }
+    })
 }
 
-fn impl_clone_struct(item_struct: &ItemStruct) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+fn impl_clone_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "clone");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_struct.ident;
 
-    match &item_struct.fields {
-        Fields::Named(fields) => {
-            let fields_0 = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
-            let fields_1 = fields_0.clone();
-
-            quote! {
-                #[automatically_derived]
-                impl #impl_generics Clone for #ty #ty_generics #where_clause {
-                    #[inline]
-                    fn clone(&self) -> #ty #ty_generics {
-                        #ty {
-                            #( #fields_0: self. #fields_0 .clone(), )*
-                        }
-                    }
-
-                    #[inline]
-                    fn clone_from(&mut self, other: &Self) {
-                        #( self.#fields_1.clone_from(&other.#fields_1); )*
-                    }
+    if let Fields::Unit = item_struct.fields {
+        return Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::clone::Clone for #ty #ty_generics #where_clause {
+                #[inline]
+                fn clone(&self) -> Self {
+                    Self
                 }
             }
-        }
-        Fields::Unnamed(fields) => {
-            let fields = (0..fields.unnamed.len()).into_iter().map(Index::from);
-
-            let clone = fields
-                .clone()
-                .map(|i| quote! { self.#i.clone() })
-                .collect::<Punctuated<_, Token![,]>>();
-
-            let clone = clone.pairs();
+        });
+    }
 
-            quote! {
-                #[automatically_derived]
-                impl #impl_generics Clone for #ty #ty_generics #where_clause {
-                    #[inline]
-                    fn clone(&self) -> #ty #ty_generics {
-                        #ty ( #( #clone )* )
-                    }
+    let members = field_members(&item_struct.fields);
+
+    let clone = item_struct
+        .fields
+        .iter()
+        .zip(&members)
+        .map(|(field, member)| match phantom_value(&field.ty) {
+            Some(value) => quote! { #member: #value },
+            None => quote! { #member: ::core::clone::Clone::clone(&self.#member) },
+        });
+
+    let clone_from = item_struct
+        .fields
+        .iter()
+        .zip(&members)
+        .filter(|(field, _)| !is_phantom(&field.ty))
+        .map(|(_, member)| member);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::clone::Clone for #ty #ty_generics #where_clause {
+            #[inline]
+            fn clone(&self) -> #ty #ty_generics {
+                #ty { #( #clone, )* }
+            }
 
-                    #[inline]
-                    fn clone_from(&mut self, other: &Self) {
-                        #( self. #fields .clone_from(&other. #fields); )*
-                    }
-                }
+            #[inline]
+            fn clone_from(&mut self, other: &Self) {
+                #( ::core::clone::Clone::clone_from(&mut self.#clone_from, &other.#clone_from); )*
             }
         }
-        Fields::Unit => {
-            quote! {
-                #[automatically_derived]
-                impl #impl_generics Clone for #ty #ty_generics #where_clause {
-                    #[inline]
-                    fn clone(&self) -> Self {
-                        Self
-                    }
-                }
+    })
+}
+
+fn impl_clone_union(item_union: &ItemUnion) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_union.attrs)?;
+    check_field_attrs(&item_union.fields.named)?;
+    let generics = attrs.generics(&item_union.generics, "clone");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_union.ident;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::clone::Clone for #ty #ty_generics #where_clause {
+            #[inline]
+            fn clone(&self) -> Self {
+                struct AssertCopy<T: ::core::marker::Copy + ?::core::marker::Sized>(
+                    ::core::marker::PhantomData<T>,
+                );
+                let _: AssertCopy<Self>;
+                *self
             }
         }
-    }
+    })
 }
 
-#[proc_macro_derive(ForceCopy)]
+#[proc_macro_derive(ForceCopy, attributes(force))]
 pub fn force_copy(input: TokenStream) -> TokenStream {
-    let item: Item = syn::parse(input).unwrap();
+    let item = parse_macro_input!(input as Item);
 
-    let tokens = match &item {
+    derive_copy(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_copy(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
         Item::Enum(item_enum) => impl_copy_enum(item_enum),
         Item::Struct(item_struct) => impl_copy_struct(item_struct),
-        _ => panic!("ForceCopy can only be implemented for enums and structs."),
-    };
-
-    tokens.into()
+        Item::Union(item_union) => impl_copy_union(item_union),
+        _ => Err(unsupported_item(item, "ForceCopy")),
+    }
 }
 
-fn impl_copy_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+fn impl_copy_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "copy");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_enum.ident;
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics Copy for #ty #ty_generics #where_clause {}
-    }
+        impl #impl_generics ::core::marker::Copy for #ty #ty_generics #where_clause {}
+    })
 }
 
-fn impl_copy_struct(item_struct: &ItemStruct) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+fn impl_copy_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "copy");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_struct.ident;
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics Copy for #ty #ty_generics #where_clause {}
-    }
+        impl #impl_generics ::core::marker::Copy for #ty #ty_generics #where_clause {}
+    })
+}
+
+fn impl_copy_union(item_union: &ItemUnion) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_union.attrs)?;
+    check_field_attrs(&item_union.fields.named)?;
+    let generics = attrs.generics(&item_union.generics, "copy");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_union.ident;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::marker::Copy for #ty #ty_generics #where_clause {}
+    })
 }
 
-#[proc_macro_derive(ForcePartialEq)]
+#[proc_macro_derive(ForcePartialEq, attributes(force))]
 pub fn force_partial_eq(input: TokenStream) -> TokenStream {
-    let item: Item = syn::parse(input).unwrap();
+    let item = parse_macro_input!(input as Item);
+
+    derive_partial_eq(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let tokens = match &item {
+fn derive_partial_eq(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
         Item::Enum(item_enum) => impl_partial_eq_enum(item_enum),
         Item::Struct(item_struct) => impl_partial_eq_struct(item_struct),
-        _ => panic!("ForcePartialEq can only be implemented for enums and structs."),
-    };
-
-    tokens.into()
+        _ => Err(unsupported_item(item, "ForcePartialEq")),
+    }
 }
 
-fn impl_partial_eq_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+fn impl_partial_eq_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "partial_eq");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_enum.ident;
 
     let variants = item_enum.variants.iter().map(|v| {
@@ -332,30 +698,30 @@ fn impl_partial_eq_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
 
         let fields = get_field_identifiers(&v.fields);
 
-        let fields_lhs = fields.iter().map(|f| Ident::new(&format!("{}_lhs", &f), f.span()));
-        let fields_rhs = fields.iter().map(|f| Ident::new(&format!("{}_rhs", &f), f.span()));
+        let (patterns_lhs, fields_lhs) = variant_bindings(&v.fields, "_lhs", "partial_eq")?;
+        let (patterns_rhs, fields_rhs) = variant_bindings(&v.fields, "_rhs", "partial_eq")?;
+        let fns = field_fns(&v.fields, "partial_eq", quote! { ::core::cmp::PartialEq::eq })?;
 
-        let equality = fields_lhs.clone()
-            .zip(fields_rhs.clone())
-            .map(|(lhs, rhs)| quote! { #lhs == #rhs })
-            .collect::<Punctuated<_, Token![&&]>>();
-        let equality = equality.pairs();
+        let equality = conjunction(
+            fields_lhs
+                .iter()
+                .zip(&fields_rhs)
+                .zip(&fns)
+                .map(|((lhs, rhs), eq)| quote! { #eq(#lhs, #rhs) }),
+        );
 
-        match &v.fields {
+        Ok(match &v.fields {
             Fields::Named(_) => {
-                let fields1 = fields.iter();
-                let fields2 = fields.iter();
-
                 quote! {
-                    (Self::#variant { #( #fields1: #fields_lhs, )* }, Self::#variant { #( #fields2: #fields_rhs, )* }) => {
-                        #( #equality )*
+                    (Self::#variant { #( #fields: #patterns_lhs, )* }, Self::#variant { #( #fields: #patterns_rhs, )* }) => {
+                        #equality
                     }
                 }
             }
             Fields::Unnamed(_) => {
                 quote! {
-                    (Self::#variant ( #( #fields_lhs, )* ), Self::#variant ( #( #fields_rhs, )* )) => {
-                        #( #equality )*
+                    (Self::#variant ( #( #patterns_lhs, )* ), Self::#variant ( #( #patterns_rhs, )* )) => {
+                        #equality
                     }
                 }
             }
@@ -364,218 +730,2008 @@ fn impl_partial_eq_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
                     (Self::#variant, Self::#variant) => true,
                 }
             }
-        }
-    });
+        })
+    }).collect::<syn::Result<Vec<_>>>()?;
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics PartialEq for #ty #ty_generics #where_clause {
+        impl #impl_generics ::core::cmp::PartialEq for #ty #ty_generics #where_clause {
             #[inline]
-            fn eq(&self, rhs: &Self) -> bool {
+            fn eq(&self, rhs: &Self) -> ::core::primitive::bool {
                 match (self, rhs) {
                     #( #variants )*
                     (_, _) => false,
                 }
             }
         }
-    }
+    })
 }
 
-fn impl_partial_eq_struct(item_struct: &ItemStruct) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+fn impl_partial_eq_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "partial_eq");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_struct.ident;
 
+    let skip = skipped_fields(&item_struct.fields, "partial_eq")?;
+    let fns = field_fns(
+        &item_struct.fields,
+        "partial_eq",
+        quote! { ::core::cmp::PartialEq::eq },
+    )?;
+
     match &item_struct.fields {
         Fields::Named(fields) => {
-            let fields = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
-
-            let equality = fields
-                .map(|i| quote! { self.#i == rhs.#i })
-                .collect::<Punctuated<_, Token![&&]>>();
+            let fields = fields
+                .named
+                .iter()
+                .zip(&skip)
+                .filter(|(_, skip)| !**skip)
+                .map(|(f, _)| f.ident.as_ref().unwrap());
 
-            let equality = equality.pairs();
+            let equality = conjunction(
+                fields
+                    .zip(&fns)
+                    .map(|(i, eq)| quote! { #eq(&self.#i, &rhs.#i) }),
+            );
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics PartialEq for #ty #ty_generics #where_clause {
+                impl #impl_generics ::core::cmp::PartialEq for #ty #ty_generics #where_clause {
                     #[inline]
-                    fn eq(&self, rhs: &Self) -> bool {
-                        #( #equality )*
+                    fn eq(&self, rhs: &Self) -> ::core::primitive::bool {
+                        #equality
                     }
                 }
-            }
+            })
         }
         Fields::Unnamed(fields) => {
-            let fields = (0..fields.unnamed.len()).into_iter().map(Index::from);
+            let fields = (0..fields.unnamed.len())
+                .filter(|i| !skip[*i])
+                .map(Index::from);
 
-            let equality = fields
-                .map(|i| quote! { self.#i == rhs.#i })
-                .collect::<Punctuated<_, Token![&&]>>();
+            let equality = conjunction(
+                fields
+                    .zip(&fns)
+                    .map(|(i, eq)| quote! { #eq(&self.#i, &rhs.#i) }),
+            );
 
-            let equality = equality.pairs();
-
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics PartialEq for #ty #ty_generics #where_clause {
+                impl #impl_generics ::core::cmp::PartialEq for #ty #ty_generics #where_clause {
                     #[inline]
-                    fn eq(&self, rhs: &Self) -> bool {
-                        #( #equality )*
+                    fn eq(&self, rhs: &Self) -> ::core::primitive::bool {
+                        #equality
                     }
                 }
-            }
+            })
         }
-        Fields::Unit => {
-            quote! {
-                #[automatically_derived]
-                impl #impl_generics PartialEq for #ty #ty_generics #where_clause {
-                    #[inline]
-                    fn eq(&self, rhs: &Self) -> bool {
-                        true
-                    }
+        Fields::Unit => Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::cmp::PartialEq for #ty #ty_generics #where_clause {
+                #[inline]
+                fn eq(&self, rhs: &Self) -> ::core::primitive::bool {
+                    true
                 }
             }
-        }
+        }),
     }
 }
 
-#[proc_macro_derive(ForceEq)]
+#[proc_macro_derive(ForceEq, attributes(force))]
 pub fn force_eq(input: TokenStream) -> TokenStream {
-    let item: Item = syn::parse(input).unwrap();
+    let item = parse_macro_input!(input as Item);
+
+    derive_eq(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let tokens = match &item {
+fn derive_eq(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
         Item::Enum(item_enum) => impl_eq_enum(item_enum),
         Item::Struct(item_struct) => impl_eq_struct(item_struct),
-        _ => panic!("ForceEq can only be implemented for enums and structs."),
-    };
-
-    tokens.into()
+        _ => Err(unsupported_item(item, "ForceEq")),
+    }
 }
 
-fn impl_eq_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+fn impl_eq_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "eq");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_enum.ident;
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics Eq for #ty #ty_generics #where_clause {}
-    }
+        impl #impl_generics ::core::cmp::Eq for #ty #ty_generics #where_clause {}
+    })
 }
 
-fn impl_eq_struct(item_struct: &ItemStruct) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+fn impl_eq_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "eq");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_struct.ident;
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics Eq for #ty #ty_generics #where_clause {}
-    }
+        impl #impl_generics ::core::cmp::Eq for #ty #ty_generics #where_clause {}
+    })
 }
 
-#[proc_macro_derive(ForceHash)]
+#[proc_macro_derive(ForceHash, attributes(force))]
 pub fn force_hash(input: TokenStream) -> TokenStream {
-    let item: Item = syn::parse(input).unwrap();
+    let item = parse_macro_input!(input as Item);
+
+    derive_hash(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    let tokens = match &item {
+fn derive_hash(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
         Item::Enum(item_enum) => impl_hash_enum(item_enum),
         Item::Struct(item_struct) => impl_hash_struct(item_struct),
-        _ => panic!("ForceHash can only be implemented for enums and structs."),
-    };
-
-    tokens.into()
+        _ => Err(unsupported_item(item, "ForceHash")),
+    }
 }
 
-fn impl_hash_enum(item_enum: &ItemEnum) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_enum.generics.split_for_impl();
+fn impl_hash_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "hash");
+    check_consistency(
+        &attrs,
+        item_enum.variants.iter().flat_map(|v| &v.fields),
+        "hash",
+        "Hash",
+    )?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_enum.ident;
 
-    let variants = item_enum.variants.iter().map(|v| {
-        let variant = &v.ident;
-
-        let fields = get_field_identifiers(&v.fields);
-        let fields1 = fields.iter();
-        let fields = fields.iter();
-
-        match &v.fields {
-            Fields::Named(_) => {
-                quote! {
-                    Self::#variant {
-                        #( #fields, )*
-                    } => {
-                        #(
-                            std::hash::Hash::hash(#fields1, state);
-                        )*
+    let variants = item_enum
+        .variants
+        .iter()
+        .map(|v| {
+            let variant = &v.ident;
+
+            let fields = get_field_identifiers(&v.fields);
+            let (patterns, fields1) = variant_bindings(&v.fields, "_value", "hash")?;
+            let fns = field_fns(&v.fields, "hash", quote! { ::core::hash::Hash::hash })?;
+
+            Ok(match &v.fields {
+                Fields::Named(_) => {
+                    quote! {
+                        Self::#variant {
+                            #( #fields: #patterns, )*
+                        } => {
+                            #(
+                                #fns(#fields1, state);
+                            )*
+                        }
                     }
                 }
-            }
-            Fields::Unnamed(_) => {
-                quote! {
-                    Self::#variant (
-                        #( #fields, )*
-                    ) => {
-                        #(
-                            std::hash::Hash::hash(#fields1, state);
-                        )*
+                Fields::Unnamed(_) => {
+                    quote! {
+                        Self::#variant (
+                            #( #patterns, )*
+                        ) => {
+                            #(
+                                #fns(#fields1, state);
+                            )*
+                        }
                     }
                 }
-            }
-            Fields::Unit => {
-                quote! {
-                    Self::#variant => {}
+                Fields::Unit => {
+                    quote! {
+                        Self::#variant => {}
+                    }
                 }
-            }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let hash = if item_enum.variants.is_empty() {
+        quote! { match *self {} }
+    } else {
+        quote! {
+            ::core::hash::Hash::hash(&::core::mem::discriminant(self), state);
+            match self {
+                #( #variants )*
+            };
         }
-    });
+    };
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #impl_generics std::hash::Hash for #ty #ty_generics #where_clause {
+        impl #impl_generics ::core::hash::Hash for #ty #ty_generics #where_clause {
             #[inline]
-            fn hash<__H: std::hash::Hasher>(&self, state: &mut __H) {
-                std::hash::Hash::hash(&std::mem::discriminant(self), state);
-                match self {
-                    #( #variants )*
-                };
+            fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                #hash
             }
         }
-    }
+    })
 }
 
-fn impl_hash_struct(item_struct: &ItemStruct) -> proc_macro2::TokenStream {
-    let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
+fn impl_hash_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "hash");
+    check_consistency(&attrs, &item_struct.fields, "hash", "Hash")?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let ty = &item_struct.ident;
 
+    let skip = skipped_fields(&item_struct.fields, "hash")?;
+    let fns = field_fns(
+        &item_struct.fields,
+        "hash",
+        quote! { ::core::hash::Hash::hash },
+    )?;
+
     match &item_struct.fields {
         Fields::Named(fields) => {
-            let fields = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
+            let fields = fields
+                .named
+                .iter()
+                .zip(&skip)
+                .filter(|(_, skip)| !**skip)
+                .map(|(f, _)| f.ident.as_ref().unwrap());
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics std::hash::Hash for #ty #ty_generics #where_clause {
-                    fn hash<H>(&self, state: &mut H) where H: std::hash::Hasher {
-                         #( self.#fields.hash(state); )*
+                impl #impl_generics ::core::hash::Hash for #ty #ty_generics #where_clause {
+                    fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                         #( #fns(&self.#fields, state); )*
                     }
                 }
-            }
+            })
         }
         Fields::Unnamed(fields) => {
-            let fields = (0..fields.unnamed.len()).into_iter().map(Index::from);
+            let fields = (0..fields.unnamed.len())
+                .filter(|i| !skip[*i])
+                .map(Index::from);
 
-            quote! {
+            Ok(quote! {
                 #[automatically_derived]
-                impl #impl_generics std::hash::Hash for #ty #ty_generics #where_clause {
-                    fn hash<H>(&self, state: &mut H) where H: std::hash::Hasher {
-                         #( self.#fields.hash(state); )*
+                impl #impl_generics ::core::hash::Hash for #ty #ty_generics #where_clause {
+                    fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {
+                         #( #fns(&self.#fields, state); )*
+                    }
+                }
+            })
+        }
+        Fields::Unit => Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::hash::Hash for #ty #ty_generics #where_clause {
+                fn hash<__H: ::core::hash::Hasher>(&self, state: &mut __H) {}
+            }
+        }),
+    }
+}
+
+fn cmp_chain(
+    cmp: &proc_macro2::TokenStream,
+    equal: &proc_macro2::TokenStream,
+    pairs: impl Iterator<Item = (proc_macro2::TokenStream, proc_macro2::TokenStream)>,
+) -> proc_macro2::TokenStream {
+    let comparisons = pairs.map(|(lhs, rhs)| {
+        quote! {
+            match #cmp(#lhs, #rhs) {
+                #equal => {}
+                cmp => return cmp,
+            }
+        }
+    });
+
+    quote! {
+        #( #comparisons )*
+        #equal
+    }
+}
+
+fn cmp_struct(
+    item_struct: &ItemStruct,
+    derive: &str,
+    cmp: &proc_macro2::TokenStream,
+    equal: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let skip = skipped_fields(&item_struct.fields, derive)?;
+
+    Ok(match &item_struct.fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .zip(&skip)
+                .filter(|(_, skip)| !**skip)
+                .map(|(f, _)| f.ident.as_ref().unwrap());
+            cmp_chain(
+                cmp,
+                equal,
+                fields.map(|i| (quote! { &self.#i }, quote! { &rhs.#i })),
+            )
+        }
+        Fields::Unnamed(fields) => {
+            let fields = (0..fields.unnamed.len())
+                .filter(|i| !skip[*i])
+                .map(Index::from);
+            cmp_chain(
+                cmp,
+                equal,
+                fields.map(|i| (quote! { &self.#i }, quote! { &rhs.#i })),
+            )
+        }
+        Fields::Unit => cmp_chain(cmp, equal, std::iter::empty()),
+    })
+}
+
+fn cmp_enum(
+    item_enum: &ItemEnum,
+    derive: &str,
+    cmp: &proc_macro2::TokenStream,
+    equal: &proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    if item_enum.variants.is_empty() {
+        return Ok(quote! { match *self {} });
+    }
+
+    let variants = item_enum.variants.iter().map(|v| {
+        let variant = &v.ident;
+
+        let fields = get_field_identifiers(&v.fields);
+
+        let (patterns_lhs, fields_lhs) = variant_bindings(&v.fields, "_lhs", derive)?;
+        let (patterns_rhs, fields_rhs) = variant_bindings(&v.fields, "_rhs", derive)?;
+
+        let comparison = cmp_chain(
+            cmp,
+            equal,
+            fields_lhs
+                .iter()
+                .zip(&fields_rhs)
+                .map(|(lhs, rhs)| (quote! { #lhs }, quote! { #rhs })),
+        );
+
+        Ok(match &v.fields {
+            Fields::Named(_) => {
+                quote! {
+                    (Self::#variant { #( #fields: #patterns_lhs, )* }, Self::#variant { #( #fields: #patterns_rhs, )* }) => {
+                        #comparison
+                    }
+                }
+            }
+            Fields::Unnamed(_) => {
+                quote! {
+                    (Self::#variant ( #( #patterns_lhs, )* ), Self::#variant ( #( #patterns_rhs, )* )) => {
+                        #comparison
+                    }
+                }
+            }
+            Fields::Unit => {
+                quote! {
+                    (Self::#variant, Self::#variant) => #equal,
+                }
+            }
+        })
+    }).collect::<syn::Result<Vec<_>>>()?;
+
+    let index_lhs = variant_index(item_enum, quote! { *self });
+    let index_rhs = variant_index(item_enum, quote! { *rhs });
+
+    Ok(quote! {
+        match (self, rhs) {
+            #( #variants )*
+            (_, _) => {
+                let index_lhs: ::core::primitive::usize = #index_lhs;
+                let index_rhs: ::core::primitive::usize = #index_rhs;
+                #cmp(&index_lhs, &index_rhs)
+            }
+        }
+    })
+}
+
+fn variant_index(
+    item_enum: &ItemEnum,
+    value: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let variants = item_enum.variants.iter().enumerate().map(|(i, v)| {
+        let variant = &v.ident;
+        quote! { Self::#variant { .. } => #i, }
+    });
+
+    quote! {
+        match #value {
+            #( #variants )*
+        }
+    }
+}
+
+#[proc_macro_derive(ForcePartialOrd, attributes(force))]
+pub fn force_partial_ord(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_partial_ord(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_partial_ord(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Enum(item_enum) => impl_partial_ord_enum(item_enum),
+        Item::Struct(item_struct) => impl_partial_ord_struct(item_struct),
+        _ => Err(unsupported_item(item, "ForcePartialOrd")),
+    }
+}
+
+fn impl_partial_ord_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "partial_ord");
+    check_consistency(
+        &attrs,
+        item_enum.variants.iter().flat_map(|v| &v.fields),
+        "partial_ord",
+        "PartialOrd",
+    )?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_enum.ident;
+
+    let comparison = cmp_enum(
+        item_enum,
+        "partial_ord",
+        &quote! { ::core::cmp::PartialOrd::partial_cmp },
+        &quote! { ::core::option::Option::Some(::core::cmp::Ordering::Equal) },
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::PartialOrd for #ty #ty_generics #where_clause {
+            #[inline]
+            fn partial_cmp(&self, rhs: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                #comparison
+            }
+        }
+    })
+}
+
+fn impl_partial_ord_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "partial_ord");
+    check_consistency(&attrs, &item_struct.fields, "partial_ord", "PartialOrd")?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let comparison = cmp_struct(
+        item_struct,
+        "partial_ord",
+        &quote! { ::core::cmp::PartialOrd::partial_cmp },
+        &quote! { ::core::option::Option::Some(::core::cmp::Ordering::Equal) },
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::PartialOrd for #ty #ty_generics #where_clause {
+            #[inline]
+            fn partial_cmp(&self, rhs: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                #comparison
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceOrd, attributes(force))]
+pub fn force_ord(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_ord(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_ord(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Enum(item_enum) => impl_ord_enum(item_enum),
+        Item::Struct(item_struct) => impl_ord_struct(item_struct),
+        _ => Err(unsupported_item(item, "ForceOrd")),
+    }
+}
+
+fn impl_ord_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "ord");
+    check_consistency(
+        &attrs,
+        item_enum.variants.iter().flat_map(|v| &v.fields),
+        "ord",
+        "Ord",
+    )?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_enum.ident;
+
+    let comparison = cmp_enum(
+        item_enum,
+        "ord",
+        &quote! { ::core::cmp::Ord::cmp },
+        &quote! { ::core::cmp::Ordering::Equal },
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::Ord for #ty #ty_generics #where_clause {
+            #[inline]
+            fn cmp(&self, rhs: &Self) -> ::core::cmp::Ordering {
+                #comparison
+            }
+        }
+    })
+}
+
+fn impl_ord_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "ord");
+    check_consistency(&attrs, &item_struct.fields, "ord", "Ord")?;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let comparison = cmp_struct(
+        item_struct,
+        "ord",
+        &quote! { ::core::cmp::Ord::cmp },
+        &quote! { ::core::cmp::Ordering::Equal },
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::cmp::Ord for #ty #ty_generics #where_clause {
+            #[inline]
+            fn cmp(&self, rhs: &Self) -> ::core::cmp::Ordering {
+                #comparison
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceDebug, attributes(force))]
+pub fn force_debug(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_debug(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_debug(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Enum(item_enum) => impl_debug_enum(item_enum),
+        Item::Struct(item_struct) => impl_debug_struct(item_struct),
+        _ => Err(unsupported_item(item, "ForceDebug")),
+    }
+}
+
+fn impl_debug_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    check_variant_attrs(item_enum)?;
+    let generics = attrs.generics(&item_enum.generics, "debug");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_enum.ident;
+
+    let variants = item_enum.variants.iter().map(|v| {
+        let variant = &v.ident;
+        let name = variant.unraw().to_string();
+
+        let fields = get_field_identifiers(&v.fields);
+        let fields1 = fields
+            .iter()
+            .map(|f| binding(f, "_value"))
+            .collect::<Vec<_>>();
+
+        match &v.fields {
+            Fields::Named(_) => {
+                let names = fields.iter().map(|f| f.unraw().to_string());
+
+                quote! {
+                    Self::#variant { #( #fields: #fields1, )* } => {
+                        f.debug_struct(#name)
+                            #( .field(#names, #fields1) )*
+                            .finish()
+                    }
+                }
+            }
+            Fields::Unnamed(_) => {
+                quote! {
+                    Self::#variant ( #( #fields1, )* ) => {
+                        f.debug_tuple(#name)
+                            #( .field(#fields1) )*
+                            .finish()
                     }
                 }
             }
+            Fields::Unit => {
+                quote! {
+                    Self::#variant => f.write_str(#name),
+                }
+            }
+        }
+    });
+
+    let debug = if item_enum.variants.is_empty() {
+        quote! { match *self {} }
+    } else {
+        quote! {
+            match self {
+                #( #variants )*
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Debug for #ty #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #debug
+            }
+        }
+    })
+}
+
+fn impl_debug_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "debug");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+    let name = ty.unraw().to_string();
+
+    let debug = match &item_struct.fields {
+        Fields::Named(fields) => {
+            let fields = fields.named.iter().map(|f| f.ident.as_ref().unwrap());
+            let names = fields.clone().map(|f| f.unraw().to_string());
+
+            quote! {
+                f.debug_struct(#name)
+                    #( .field(#names, &self.#fields) )*
+                    .finish()
+            }
+        }
+        Fields::Unnamed(fields) => {
+            let fields = (0..fields.unnamed.len()).map(Index::from);
+
+            quote! {
+                f.debug_tuple(#name)
+                    #( .field(&self.#fields) )*
+                    .finish()
+            }
         }
         Fields::Unit => {
             quote! {
-                #[automatically_derived]
-                impl #impl_generics std::hash::Hash for #ty #ty_generics #where_clause {
-                    fn hash<H>(&self, state: &mut H) where H: std::hash::Hasher {}
+                f.write_str(#name)
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Debug for #ty #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #debug
+            }
+        }
+    })
+}
+
+/// Rewrites a `#[force(display = "...")]` format string so that each placeholder names a format
+/// argument `__<index>` for the field it refers to, by name, by position or implicitly. Width and
+/// precision arguments (`{:>1$}`, `{:.prec$}`, `{:.*}`) are rewritten the same way. Returns the
+/// new string and the indices of the fields it uses.
+fn display_format(lit: &LitStr, fields: &Fields) -> syn::Result<(LitStr, Vec<usize>)> {
+    let value = lit.value();
+    let mut format = String::new();
+    let mut used = vec![];
+    let mut next = 0;
+
+    let field_index = |arg: &str| {
+        let index = match arg.parse::<usize>() {
+            Ok(index) => Some(index),
+            Err(_) => fields
+                .iter()
+                .position(|f| f.ident.as_ref().is_some_and(|i| i.unraw() == arg)),
+        };
+
+        index.filter(|&index| index < fields.len()).ok_or_else(|| {
+            syn::Error::new_spanned(
+                lit,
+                format!("unknown field `{}` in display format string", arg),
+            )
+        })
+    };
+
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                "unterminated `{` in display format string",
+                            ))
+                        }
+                    }
+                }
+
+                let (arg, spec) =
+                    placeholder.split_at(placeholder.find(':').unwrap_or(placeholder.len()));
+                let arg = arg.trim();
+
+                // `.*` takes its precision from the next implicit argument, before the value.
+                let mut indices = vec![];
+                let mut rewritten = String::new();
+                let mut word = String::new();
+                for c in spec.chars() {
+                    if c.is_alphanumeric() || c == '_' {
+                        word.push(c);
+                        continue;
+                    }
+
+                    if c == '$' && !word.is_empty() {
+                        let index = field_index(&word)?;
+                        rewritten.push_str(&format!("__{}$", index));
+                        indices.push(index);
+                    } else if c == '*' && word.is_empty() && rewritten.ends_with('.') {
+                        rewritten.push_str(&format!("__{}$", next));
+                        indices.push(next);
+                        next += 1;
+                    } else {
+                        rewritten.push_str(&word);
+                        rewritten.push(c);
+                    }
+                    word.clear();
+                }
+                rewritten.push_str(&word);
+
+                let index = if arg.is_empty() {
+                    next += 1;
+                    next - 1
+                } else {
+                    field_index(arg)?
+                };
+                indices.push(index);
+
+                format.push_str(&format!("{{__{}{}}}", index, rewritten));
+                for index in indices {
+                    if !used.contains(&index) {
+                        used.push(index);
+                    }
                 }
             }
+            c => format.push(c),
+        }
+    }
+
+    if next > fields.len() {
+        return Err(syn::Error::new_spanned(
+            lit,
+            format!(
+                "format string has {} {} but only {} {}",
+                next,
+                if next == 1 {
+                    "placeholder"
+                } else {
+                    "placeholders"
+                },
+                fields.len(),
+                if fields.len() == 1 { "field" } else { "fields" },
+            ),
+        ));
+    }
+
+    Ok((LitStr::new(&format, lit.span()), used))
+}
+
+/// Returns the `Display` body for a struct or variant: the formatted `display` string if given,
+/// or else `name` for unit shapes.
+fn display_body(
+    display: Option<&LitStr>,
+    fields: &Fields,
+    name: &Ident,
+    field: impl Fn(&Member) -> proc_macro2::TokenStream,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let display = match (display, fields) {
+        (Some(display), _) => display,
+        (None, Fields::Unit) => {
+            let name = name.unraw().to_string();
+            return Ok(quote! { f.write_str(#name) });
+        }
+        (None, _) => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "ForceDisplay requires `#[force(display = \"...\")]` for types with fields",
+            ))
+        }
+    };
+
+    let (format, used) = display_format(display, fields)?;
+    let members = field_members(fields);
+
+    let args = used.iter().map(|i| {
+        let arg = Ident::new(&format!("__{}", i), Span::call_site());
+        let value = field(&members[*i]);
+        quote! { #arg = #value }
+    });
+
+    Ok(quote! { ::core::write!(f, #format, #( #args ),*) })
+}
+
+#[proc_macro_derive(ForceDisplay, attributes(force))]
+pub fn force_display(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_display(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_display(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Enum(item_enum) => impl_display_enum(item_enum),
+        Item::Struct(item_struct) => impl_display_struct(item_struct),
+        _ => Err(unsupported_item(item, "ForceDisplay")),
+    }
+}
+
+fn impl_display_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    let generics = attrs.generics(&item_enum.generics, "display");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_enum.ident;
+
+    if let Some(display) = &attrs.display {
+        return Err(syn::Error::new_spanned(
+            display,
+            "enums take `#[force(display = \"...\")]` on each variant",
+        ));
+    }
+
+    let variants = item_enum
+        .variants
+        .iter()
+        .map(|v| {
+            let variant = &v.ident;
+            let display = VariantAttrs::parse(&v.attrs)?.display;
+
+            let members = field_members(&v.fields);
+            let bindings = (0..members.len())
+                .map(|i| Ident::new(&format!("__{}", i), Span::call_site()))
+                .collect::<Vec<_>>();
+
+            let body = display_body(display.as_ref(), &v.fields, variant, |member| {
+                let i = members.iter().position(|m| m == member).unwrap();
+                let binding = &bindings[i];
+                quote! { #binding }
+            })?;
+
+            Ok(quote! {
+                Self::#variant { #( #members: #bindings, )* } => #body,
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let display = if item_enum.variants.is_empty() {
+        quote! { match *self {} }
+    } else {
+        quote! {
+            match self {
+                #( #variants )*
+            }
+        }
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Display for #ty #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #display
+            }
+        }
+    })
+}
+
+fn impl_display_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "display");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let display = display_body(
+        attrs.display.as_ref(),
+        &item_struct.fields,
+        ty,
+        |member| quote! { &self.#member },
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::fmt::Display for #ty #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #display
+            }
+        }
+    })
+}
+
+/// Returns the field targeted by `Deref` and `DerefMut`: the one marked `#[force(deref)]`, or
+/// else the only field that is not `PhantomData`.
+fn deref_field<'a>(item_struct: &'a ItemStruct, derive: &str) -> syn::Result<(Member, &'a Type)> {
+    let mut marked = vec![];
+    let mut candidates = vec![];
+
+    for (field, member) in item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+    {
+        if FieldAttrs::parse(&field.attrs)?.deref {
+            marked.push((field, member));
+        } else if !is_phantom(&field.ty) {
+            candidates.push((field, member));
+        }
+    }
+
+    let fields = if marked.is_empty() {
+        candidates
+    } else {
+        marked
+    };
+
+    match fields.len() {
+        0 => Err(syn::Error::new_spanned(
+            &item_struct.ident,
+            format!(
+                "{} requires a field that is not PhantomData or one marked `#[force(deref)]`",
+                derive
+            ),
+        )),
+        1 => {
+            let (field, member) = fields.into_iter().next().unwrap();
+            Ok((member, &field.ty))
+        }
+        _ => Err(fields
+            .iter()
+            .map(|(field, _)| {
+                syn::Error::new_spanned(
+                    field,
+                    format!(
+                        "{} target is ambiguous; mark exactly one field with `#[force(deref)]`",
+                        derive
+                    ),
+                )
+            })
+            .reduce(|mut errors, error| {
+                errors.combine(error);
+                errors
+            })
+            .unwrap()),
+    }
+}
+
+fn unsupported_non_struct(item: &Item, derive: &str) -> syn::Error {
+    let message = format!("{} can only be implemented for structs", derive);
+
+    match item {
+        Item::Enum(item_enum) => syn::Error::new(item_enum.enum_token.span, message),
+        Item::Union(item_union) => syn::Error::new(item_union.union_token.span, message),
+        _ => syn::Error::new_spanned(item, message),
+    }
+}
+
+#[proc_macro_derive(ForceDeref, attributes(force))]
+pub fn force_deref(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_deref(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_deref(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_deref_struct(item_struct),
+        _ => Err(unsupported_non_struct(item, "ForceDeref")),
+    }
+}
+
+fn impl_deref_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "deref");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let (member, target) = deref_field(item_struct, "ForceDeref")?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::Deref for #ty #ty_generics #where_clause {
+            type Target = #target;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                &self.#member
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceDerefMut, attributes(force))]
+pub fn force_deref_mut(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_deref_mut(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_deref_mut(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_deref_mut_struct(item_struct),
+        _ => Err(unsupported_non_struct(item, "ForceDerefMut")),
+    }
+}
+
+fn impl_deref_mut_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "deref_mut");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let (member, _) = deref_field(item_struct, "ForceDerefMut")?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::DerefMut for #ty #ty_generics #where_clause {
+            #[inline]
+            fn deref_mut(&mut self) -> &mut <Self as ::core::ops::Deref>::Target {
+                &mut self.#member
+            }
+        }
+    })
+}
+
+/// Returns the only field of a newtype that is not `PhantomData`.
+fn newtype_field<'a>(item_struct: &'a ItemStruct, derive: &str) -> syn::Result<(Member, &'a Type)> {
+    let mut fields = item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+        .filter(|(f, _)| !is_phantom(&f.ty));
+
+    match (fields.next(), fields.next()) {
+        (Some((field, member)), None) => Ok((member, &field.ty)),
+        _ => Err(syn::Error::new_spanned(
+            &item_struct.ident,
+            format!(
+                "{} requires exactly one field that is not PhantomData",
+                derive
+            ),
+        )),
+    }
+}
+
+/// Returns the field initialisers building a newtype from `value`, filling in phantom markers.
+fn newtype_fields(
+    item_struct: &ItemStruct,
+    inner: &Member,
+    value: proc_macro2::TokenStream,
+) -> Vec<proc_macro2::TokenStream> {
+    item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+        .map(|(field, member)| match phantom_value(&field.ty) {
+            Some(marker) if member != *inner => quote! { #member: #marker },
+            _ => quote! { #member: #value },
+        })
+        .collect()
+}
+
+fn field_members(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match &f.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(i)),
+        })
+        .collect()
+}
+
+#[proc_macro_derive(ForceFrom, attributes(force))]
+pub fn force_from(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_from(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_from(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Enum(item_enum) => impl_from_enum(item_enum),
+        Item::Struct(item_struct) => impl_from_struct(item_struct),
+        _ => Err(unsupported_item(item, "ForceFrom")),
+    }
+}
+
+/// Implements `From<Payload>` for each variant with a single field that is not `PhantomData`.
+/// Variants marked `#[force(skip(from))]` are left out. Duplicate payloads are only detected
+/// when spelled the same way, so `u32` and an alias of it conflict as overlapping impls instead.
+fn impl_from_enum(item_enum: &ItemEnum) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_enum.attrs)?;
+    check_field_attrs(item_enum.variants.iter().flat_map(|v| &v.fields))?;
+    let generics = attrs.generics(&item_enum.generics, "from");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_enum.ident;
+
+    let mut payloads = vec![];
+    let mut impls = vec![];
+
+    for v in &item_enum.variants {
+        let variant = &v.ident;
+
+        if VariantAttrs::parse(&v.attrs)?.skip_from {
+            continue;
+        }
+
+        let field = match v.fields.iter().collect::<Vec<_>>()[..] {
+            [field] if !is_phantom(&field.ty) => field,
+            _ => continue,
+        };
+        let payload = &field.ty;
+        let payload_name = quote! { #payload }.to_string();
+
+        if payloads.contains(&payload_name) {
+            return Err(syn::Error::new_spanned(
+                payload,
+                format!(
+                    "ForceFrom cannot implement From<{}> for more than one variant; \
+                     skip the others with #[force(skip(from))]",
+                    payload_name
+                ),
+            ));
+        }
+        payloads.push(payload_name);
+
+        let member = &field_members(&v.fields)[0];
+
+        impls.push(quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::From<#payload> for #ty #ty_generics #where_clause {
+                #[inline]
+                fn from(value: #payload) -> Self {
+                    Self::#variant { #member: value }
+                }
+            }
+        });
+    }
+
+    Ok(quote! { #( #impls )* })
+}
+
+fn impl_from_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "from");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let (inner, inner_ty) = newtype_field(item_struct, "ForceFrom")?;
+    let fields = newtype_fields(item_struct, &inner, quote! { value });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#inner_ty> for #ty #ty_generics #where_clause {
+            #[inline]
+            fn from(value: #inner_ty) -> Self {
+                Self { #( #fields, )* }
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceInto, attributes(force))]
+pub fn force_into(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_into(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_into(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_into_struct(item_struct),
+        _ => Err(unsupported_non_struct(item, "ForceInto")),
+    }
+}
+
+/// Implements `From<Newtype>` for the inner type, the inverse of `ForceFrom`.
+fn impl_into_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "into");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let (inner, inner_ty) = newtype_field(item_struct, "ForceInto")?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#ty #ty_generics> for #inner_ty #where_clause {
+            #[inline]
+            fn from(value: #ty #ty_generics) -> Self {
+                value.#inner
+            }
+        }
+    })
+}
+
+/// Returns a constant expression for the all-zero value of `ty`: a literal for primitives,
+/// pointers, arrays, tuples and phantom markers, or else a call to `<ty>::zeroed()`, which fails to
+/// compile unless the type provides it, e.g. through `ForceZeroed`. Array elements are built in
+/// an inline `const` block, so the element type need not be `Copy`.
+fn zeroed_value(ty: &Type) -> proc_macro2::TokenStream {
+    if let Some(value) = phantom_value(ty) {
+        return value;
+    }
+
+    match ty {
+        Type::Path(_) => match primitive(ty).map(Ident::to_string).as_deref() {
+            Some("f32") | Some("f64") => quote! { 0.0 },
+            Some("bool") => quote! { false },
+            Some("char") => quote! { '\0' },
+            Some(_) => quote! { 0 },
+            None => quote_spanned! {ty.span()=> <#ty>::zeroed() },
+        },
+        Type::Ptr(ptr) if ptr.mutability.is_some() => quote! { ::core::ptr::null_mut() },
+        Type::Ptr(_) => quote! { ::core::ptr::null() },
+        Type::Array(array) => {
+            let elem = zeroed_value(&array.elem);
+            let len = &array.len;
+            quote! { [const { #elem }; #len] }
+        }
+        Type::Tuple(tuple) => {
+            let elems = tuple.elems.iter().map(zeroed_value);
+            quote! { ( #( #elems, )* ) }
+        }
+        Type::Paren(paren) => zeroed_value(&paren.elem),
+        Type::Group(group) => zeroed_value(&group.elem),
+        _ => quote_spanned! {ty.span()=> <#ty>::zeroed() },
+    }
+}
+
+#[proc_macro_derive(ForceZeroed, attributes(force))]
+pub fn force_zeroed(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_zeroed(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_zeroed(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_zeroed_struct(item_struct),
+        _ => Err(unsupported_non_struct(item, "ForceZeroed")),
+    }
+}
+
+/// Adds a `const fn zeroed()` constructor setting every field to zero. Proc-macro crates cannot
+/// export traits, so nested types are zeroed through their own `zeroed()` associated function.
+fn impl_zeroed_struct(item_struct: &ItemStruct) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, "zeroed");
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let fields = item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+        .map(|(field, member)| {
+            let value = zeroed_value(&field.ty);
+            quote! { #member: #value }
+        });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #ty #ty_generics #where_clause {
+            /// Returns the value with every field set to zero.
+            #[inline]
+            pub const fn zeroed() -> Self {
+                Self { #( #fields, )* }
+            }
+        }
+    })
+}
+
+fn derive_binary_op(
+    item: &Item,
+    derive: &str,
+    op: &str,
+    method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_binary_op_struct(item_struct, derive, op, method),
+        _ => Err(unsupported_non_struct(item, derive)),
+    }
+}
+
+/// Implements the operator trait `op` field-wise, both by value and for `&lhs op &rhs`.
+/// Phantom fields are constructed rather than combined. Operators declared with a different
+/// `rhs` or `output` are added by `impl_operator`, replacing the `Self op Self` impl if they
+/// leave `rhs` unset.
+fn impl_binary_op_struct(
+    item_struct: &ItemStruct,
+    derive: &str,
+    op: &str,
+    method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, method);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let operators = attrs
+        .operators
+        .iter()
+        .filter(|o| o.derive == method)
+        .collect::<Vec<_>>();
+    let replaces_self = operators.iter().any(|o| o.rhs.is_none());
+
+    let op = Ident::new(op, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+
+    let operators = operators
+        .into_iter()
+        .map(|o| impl_operator(item_struct, &generics, derive, &op, &method, o))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    if replaces_self {
+        return Ok(quote! { #( #operators )* });
+    }
+
+    let fields = |lhs: proc_macro2::TokenStream, rhs: proc_macro2::TokenStream| {
+        item_struct
+            .fields
+            .iter()
+            .zip(field_members(&item_struct.fields))
+            .map(|(field, member)| match phantom_value(&field.ty) {
+                Some(value) => quote! { #member: #value },
+                None => quote! {
+                    #member: ::core::ops::#op::#method(#lhs.#member, #rhs.#member)
+                },
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let owned = fields(quote! { self }, quote! { rhs });
+    let borrowed = fields(quote! { &self }, quote! { &rhs });
+
+    Ok(quote! {
+        #( #operators )*
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op for #ty #ty_generics #where_clause {
+            type Output = Self;
+
+            #[inline]
+            fn #method(self, rhs: Self) -> Self {
+                Self { #( #owned, )* }
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op<&#ty #ty_generics> for &#ty #ty_generics #where_clause {
+            type Output = #ty #ty_generics;
+
+            #[inline]
+            fn #method(self, rhs: &#ty #ty_generics) -> #ty #ty_generics {
+                #ty { #( #borrowed, )* }
+            }
+        }
+    })
+}
+
+/// Implements `Self op Rhs -> Output` for a newtype, by value and by reference, as declared by
+/// e.g. `#[force(mul(rhs = "...", output = "..."))]`. The inner field is combined with
+/// `rhs.inner` if `rhs` is `Self`, with `rhs` itself if it is a primitive, or else with
+/// `Deref::deref(&rhs)`, as given by `ForceDeref`. The result is `Self` or is converted with
+/// `From`, as given by `ForceFrom`. Missing impls are reported at the attribute's types.
+fn impl_operator(
+    item_struct: &ItemStruct,
+    generics: &Generics,
+    derive: &str,
+    op: &Ident,
+    method: &Ident,
+    operator: &Operator,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let (inner, _) = newtype_field(item_struct, derive)?;
+
+    let self_ty = quote! { #ty #ty_generics };
+    let rhs_ty = match &operator.rhs {
+        Some(rhs) => quote! { #rhs },
+        None => self_ty.clone(),
+    };
+    let output_ty = match &operator.output {
+        Some(output) => quote! { #output },
+        None => self_ty.clone(),
+    };
+
+    let (rhs_owned, rhs_borrowed) = match &operator.rhs {
+        Some(rhs) if primitive(rhs).is_some() => (quote! { rhs }, quote! { rhs }),
+        Some(rhs) => (
+            quote_spanned! {rhs.span()=> ::core::ops::Deref::deref(&rhs) },
+            quote_spanned! {rhs.span()=> ::core::ops::Deref::deref(rhs) },
+        ),
+        None => (quote! { rhs.#inner }, quote! { &rhs.#inner }),
+    };
+
+    let output = |value: proc_macro2::TokenStream| match &operator.output {
+        Some(output) => quote_spanned! {output.span()=>
+            {
+                let value = #value;
+                ::core::convert::From::from(value)
+            }
+        },
+        None => {
+            let fields = newtype_fields(item_struct, &inner, value);
+            quote! { #ty { #( #fields, )* } }
+        }
+    };
+
+    let owned = output(quote! { ::core::ops::#op::#method(self.#inner, #rhs_owned) });
+    let borrowed = output(quote! { ::core::ops::#op::#method(&self.#inner, #rhs_borrowed) });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op<#rhs_ty> for #self_ty #where_clause {
+            type Output = #output_ty;
+
+            #[inline]
+            fn #method(self, rhs: #rhs_ty) -> #output_ty {
+                #owned
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op<&#rhs_ty> for &#self_ty #where_clause {
+            type Output = #output_ty;
+
+            #[inline]
+            fn #method(self, rhs: &#rhs_ty) -> #output_ty {
+                #borrowed
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceAdd, attributes(force))]
+pub fn force_add(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_add(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_add(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_binary_op(item, "ForceAdd", "Add", "add")
+}
+
+#[proc_macro_derive(ForceSub, attributes(force))]
+pub fn force_sub(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_sub(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_sub(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_binary_op(item, "ForceSub", "Sub", "sub")
+}
+
+#[proc_macro_derive(ForceMul, attributes(force))]
+pub fn force_mul(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_mul(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_mul(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_binary_op(item, "ForceMul", "Mul", "mul")
+}
+
+#[proc_macro_derive(ForceDiv, attributes(force))]
+pub fn force_div(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_div(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_div(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_binary_op(item, "ForceDiv", "Div", "div")
+}
+
+fn derive_assign_op(
+    item: &Item,
+    derive: &str,
+    op: &str,
+    method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_assign_op_struct(item_struct, op, method),
+        _ => Err(unsupported_non_struct(item, derive)),
+    }
+}
+
+/// Implements the compound assignment trait `op` by applying it to each non-phantom field.
+fn impl_assign_op_struct(
+    item_struct: &ItemStruct,
+    op: &str,
+    method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, method);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let op = Ident::new(op, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+
+    let fields = item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+        .filter(|(field, _)| !is_phantom(&field.ty))
+        .map(|(_, member)| member);
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op for #ty #ty_generics #where_clause {
+            #[inline]
+            fn #method(&mut self, rhs: Self) {
+                #( ::core::ops::#op::#method(&mut self.#fields, rhs.#fields); )*
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceAddAssign, attributes(force))]
+pub fn force_add_assign(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_add_assign(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_add_assign(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_assign_op(item, "ForceAddAssign", "AddAssign", "add_assign")
+}
+
+#[proc_macro_derive(ForceSubAssign, attributes(force))]
+pub fn force_sub_assign(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_sub_assign(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_sub_assign(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_assign_op(item, "ForceSubAssign", "SubAssign", "sub_assign")
+}
+
+#[proc_macro_derive(ForceMulAssign, attributes(force))]
+pub fn force_mul_assign(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_mul_assign(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_mul_assign(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_assign_op(item, "ForceMulAssign", "MulAssign", "mul_assign")
+}
+
+#[proc_macro_derive(ForceDivAssign, attributes(force))]
+pub fn force_div_assign(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_div_assign(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_div_assign(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_assign_op(item, "ForceDivAssign", "DivAssign", "div_assign")
+}
+
+fn derive_unary_op(
+    item: &Item,
+    derive: &str,
+    op: &str,
+    method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_unary_op_struct(item_struct, op, method),
+        _ => Err(unsupported_non_struct(item, derive)),
+    }
+}
+
+/// Implements the unary operator trait `op` field-wise, constructing phantom fields.
+fn impl_unary_op_struct(
+    item_struct: &ItemStruct,
+    op: &str,
+    method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, method);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let op = Ident::new(op, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+
+    let fields = item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+        .map(|(field, member)| match phantom_value(&field.ty) {
+            Some(value) => quote! { #member: #value },
+            None => quote! { #member: ::core::ops::#op::#method(self.#member) },
+        });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::ops::#op for #ty #ty_generics #where_clause {
+            type Output = Self;
+
+            #[inline]
+            fn #method(self) -> Self {
+                Self { #( #fields, )* }
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceNeg, attributes(force))]
+pub fn force_neg(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_neg(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_neg(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_unary_op(item, "ForceNeg", "Neg", "neg")
+}
+
+#[proc_macro_derive(ForceNot, attributes(force))]
+pub fn force_not(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_not(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_not(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    derive_unary_op(item, "ForceNot", "Not", "not")
+}
+
+/// Implements `Sum` or `Product` for owned and borrowed items by folding with the operator `op`,
+/// as given by `ForceAdd` or `ForceMul`. The initial value has each field set to the sum or product
+/// of no values of its own type, and phantom fields constructed.
+fn impl_fold_struct(
+    item_struct: &ItemStruct,
+    fold: &str,
+    method: &str,
+    op: &str,
+    op_method: &str,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let attrs = ContainerAttrs::parse(&item_struct.attrs)?;
+    check_field_attrs(&item_struct.fields)?;
+    let generics = attrs.generics(&item_struct.generics, method);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ty = &item_struct.ident;
+
+    let mut ref_generics = generics.clone();
+    ref_generics.params.insert(0, parse_quote! { '__a });
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+
+    let fold = Ident::new(fold, Span::call_site());
+    let method = Ident::new(method, Span::call_site());
+    let op = Ident::new(op, Span::call_site());
+    let op_method = Ident::new(op_method, Span::call_site());
+
+    let fields = item_struct
+        .fields
+        .iter()
+        .zip(field_members(&item_struct.fields))
+        .map(|(field, member)| {
+            let ty = &field.ty;
+            match phantom_value(ty) {
+                Some(value) => quote! { #member: #value },
+                None => quote! {
+                    #member: ::core::iter::#fold::#method(::core::iter::empty::<#ty>())
+                },
+            }
+        });
+    let init = quote! { #ty { #( #fields, )* } };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::core::iter::#fold for #ty #ty_generics #where_clause {
+            #[inline]
+            fn #method<__I: ::core::iter::Iterator<Item = Self>>(iter: __I) -> Self {
+                ::core::iter::Iterator::fold(iter, #init, ::core::ops::#op::#op_method)
+            }
+        }
+
+        #[automatically_derived]
+        impl #ref_impl_generics ::core::iter::#fold<&'__a #ty #ty_generics> for #ty #ty_generics #where_clause {
+            #[inline]
+            fn #method<__I: ::core::iter::Iterator<Item = &'__a Self>>(iter: __I) -> Self {
+                ::core::iter::Iterator::fold(iter, #init, |acc, item| {
+                    ::core::ops::#op::#op_method(&acc, item)
+                })
+            }
+        }
+    })
+}
+
+#[proc_macro_derive(ForceSum, attributes(force))]
+pub fn force_sum(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_sum(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_sum(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => impl_fold_struct(item_struct, "Sum", "sum", "Add", "add"),
+        _ => Err(unsupported_non_struct(item, "ForceSum")),
+    }
+}
+
+#[proc_macro_derive(ForceProduct, attributes(force))]
+pub fn force_product(input: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(input as Item);
+
+    derive_product(&item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_product(item: &Item) -> syn::Result<proc_macro2::TokenStream> {
+    match item {
+        Item::Struct(item_struct) => {
+            impl_fold_struct(item_struct, "Product", "product", "Mul", "mul")
+        }
+        _ => Err(unsupported_non_struct(item, "ForceProduct")),
+    }
+}
+
+/// Shorthands accepted by `#[force_derive(...)]`, expanding to several traits.
+const PRESETS: &[(&str, &[&str])] = &[(
+    "id",
+    &["Default", "Clone", "Copy", "PartialEq", "Eq", "Hash"],
+)];
+
+/// Implements several traits at once, e.g. `#[force_derive(Clone, Copy, PartialEq)]`, producing
+/// the same impls as the corresponding `Force*` derives. `#[force(...)]` helper attributes are
+/// consumed by the macro and removed from the item, unless a further `#[force_derive(...)]`, or a
+/// `#[derive(...)]` naming a `Force*` macro, on the same item still reads them.
+#[proc_macro_attribute]
+pub fn force_derive(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args with Punctuated::<Ident, Token![,]>::parse_terminated);
+    let mut item = parse_macro_input!(input as Item);
+
+    let mut derives: Vec<Ident> = vec![];
+    let mut errors = vec![];
+
+    for arg in &args {
+        let names = match PRESETS.iter().find(|(preset, _)| arg == preset) {
+            Some((_, names)) => names.iter().map(|n| Ident::new(n, arg.span())).collect(),
+            None => vec![arg.clone()],
+        };
+
+        for name in names {
+            if derives.contains(&name) {
+                errors.push(syn::Error::new_spanned(
+                    arg,
+                    format!("`{}` is listed more than once", name),
+                ));
+            } else {
+                derives.push(name);
+            }
+        }
+    }
+
+    let impls = derives
+        .iter()
+        .map(|derive| match derive.to_string().as_str() {
+            "Default" => derive_default(&item),
+            "Clone" => derive_clone(&item),
+            "Copy" => derive_copy(&item),
+            "PartialEq" => derive_partial_eq(&item),
+            "Eq" => derive_eq(&item),
+            "Hash" => derive_hash(&item),
+            "PartialOrd" => derive_partial_ord(&item),
+            "Ord" => derive_ord(&item),
+            "Debug" => derive_debug(&item),
+            "Display" => derive_display(&item),
+            "Deref" => derive_deref(&item),
+            "DerefMut" => derive_deref_mut(&item),
+            "From" => derive_from(&item),
+            "Into" => derive_into(&item),
+            "Zeroed" => derive_zeroed(&item),
+            "Add" => derive_add(&item),
+            "Sub" => derive_sub(&item),
+            "Mul" => derive_mul(&item),
+            "Div" => derive_div(&item),
+            "AddAssign" => derive_add_assign(&item),
+            "SubAssign" => derive_sub_assign(&item),
+            "MulAssign" => derive_mul_assign(&item),
+            "DivAssign" => derive_div_assign(&item),
+            "Neg" => derive_neg(&item),
+            "Not" => derive_not(&item),
+            "Sum" => derive_sum(&item),
+            "Product" => derive_product(&item),
+            _ => Err(syn::Error::new_spanned(
+                derive,
+                format!("unknown force_derive trait `{}`", derive),
+            )),
+        })
+        .map(|tokens| tokens.unwrap_or_else(syn::Error::into_compile_error))
+        .collect::<Vec<_>>();
+
+    let errors = errors.into_iter().map(syn::Error::into_compile_error);
+
+    let later = later_derives(&item);
+    let force = !later.iter().any(|name| name.starts_with("Force"));
+    let default = derives.iter().any(|d| d == "Default")
+        && !later
+            .iter()
+            .any(|name| name == "ForceDefault" || name == "Default");
+
+    remove_helper_attributes(&mut item, force, default);
+
+    let tokens = quote! {
+        #item
+        #( #impls )*
+        #( #errors )*
+    };
+
+    tokens.into()
+}
+
+/// Names of the derives that expand after this `force_derive` and may read its helper
+/// attributes: those in the item's own `#[derive(...)]` attributes, and those of any further
+/// `#[force_derive(...)]`, given as their `Force*` equivalents.
+fn later_derives(item: &Item) -> Vec<String> {
+    let attrs = match item {
+        Item::Enum(item_enum) => &item_enum.attrs,
+        Item::Struct(item_struct) => &item_struct.attrs,
+        Item::Union(item_union) => &item_union.attrs,
+        _ => return vec![],
+    };
+
+    let is_named =
+        |a: &Attribute, name: &str| a.path.segments.last().is_some_and(|s| s.ident == name);
+    let mut names = vec![];
+
+    for attr in attrs {
+        if attr.path.is_ident("derive") {
+            let paths = attr
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .unwrap_or_default();
+            names.extend(
+                paths
+                    .iter()
+                    .filter_map(|path| path.segments.last())
+                    .map(|segment| segment.ident.to_string()),
+            );
+        } else if is_named(attr, "force_derive") {
+            let args = attr
+                .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+                .unwrap_or_default();
+            for arg in &args {
+                match PRESETS.iter().find(|(preset, _)| arg == preset) {
+                    Some((_, presets)) => {
+                        names.extend(presets.iter().map(|name| format!("Force{}", name)))
+                    }
+                    None => names.push(format!("Force{}", arg)),
+                }
+            }
+        }
+    }
+
+    names
+}
+
+fn remove_helper_attributes(item: &mut Item, force: bool, default: bool) {
+    let is_helper = |a: &Attribute| {
+        (force && a.path.is_ident("force")) || (default && a.path.is_ident("default"))
+    };
+    let retain = |attrs: &mut Vec<Attribute>| attrs.retain(|a| !is_helper(a));
+
+    match item {
+        Item::Enum(item_enum) => {
+            retain(&mut item_enum.attrs);
+            for variant in &mut item_enum.variants {
+                retain(&mut variant.attrs);
+                variant.fields.iter_mut().for_each(|f| retain(&mut f.attrs));
+            }
+        }
+        Item::Struct(item_struct) => {
+            retain(&mut item_struct.attrs);
+            item_struct
+                .fields
+                .iter_mut()
+                .for_each(|f| retain(&mut f.attrs));
+        }
+        Item::Union(item_union) => {
+            retain(&mut item_union.attrs);
+            item_union
+                .fields
+                .named
+                .iter_mut()
+                .for_each(|f| retain(&mut f.attrs));
         }
+        _ => {}
     }
 }
@@ -5,10 +5,20 @@ use std::collections::hash_map::RandomState;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::marker::PhantomData;
 
-#[derive(Debug)]
+#[allow(dead_code)]
 struct DebugOnly;
 
-#[derive(Debug, ForceDefault, ForceClone, ForceCopy, ForceEq, ForcePartialEq, ForceHash)]
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceCopy,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
 pub struct TupleStruct<T>(u32, PhantomData<T>);
 
 #[cfg(test)]
@@ -37,6 +47,31 @@ fn tuple_struct_hash() {
     assert!(!set.insert(TupleStruct::default()));
 }
 
+#[test]
+fn tuple_struct_partial_ord() {
+    assert!(TupleStruct::<DebugOnly>::new(0) < TupleStruct::new(1));
+    assert!(TupleStruct::<DebugOnly>::new(1) > TupleStruct::new(0));
+    assert!(TupleStruct::<DebugOnly>::new(1) <= TupleStruct::new(1));
+}
+
+#[test]
+fn tuple_struct_ord() {
+    let mut set = std::collections::BTreeSet::<TupleStruct<DebugOnly>>::default();
+    assert!(set.insert(TupleStruct::new(1)));
+    assert!(set.insert(TupleStruct::new(0)));
+    assert!(!set.insert(TupleStruct::new(1)));
+    assert_eq!(Some(&TupleStruct::new(0)), set.iter().next());
+}
+
+#[test]
+fn tuple_struct_debug() {
+    assert_eq!(
+        "TupleStruct(1, PhantomData<tests::DebugOnly>)",
+        format!("{:?}", TupleStruct::<DebugOnly>::new(1))
+    );
+}
+
+#[allow(dropping_copy_types)]
 #[test]
 fn tuple_struct_copy() {
     let value = TupleStruct::<DebugOnly>::default();
@@ -45,7 +80,17 @@ fn tuple_struct_copy() {
     drop(copy);
 }
 
-#[derive(Debug, ForceDefault, ForceClone, ForceCopy, ForceEq, ForcePartialEq, ForceHash)]
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceCopy,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
 pub struct FieldStruct<T> {
     value: u32,
     marker: PhantomData<T>,
@@ -80,6 +125,31 @@ fn field_struct_hash() {
     assert!(!set.insert(FieldStruct::default()));
 }
 
+#[test]
+fn field_struct_partial_ord() {
+    assert!(FieldStruct::<DebugOnly>::new(0) < FieldStruct::new(1));
+    assert!(FieldStruct::<DebugOnly>::new(1) > FieldStruct::new(0));
+    assert!(FieldStruct::<DebugOnly>::new(1) <= FieldStruct::new(1));
+}
+
+#[test]
+fn field_struct_ord() {
+    let mut set = std::collections::BTreeSet::<FieldStruct<DebugOnly>>::default();
+    assert!(set.insert(FieldStruct::new(1)));
+    assert!(set.insert(FieldStruct::new(0)));
+    assert!(!set.insert(FieldStruct::new(1)));
+    assert_eq!(Some(&FieldStruct::new(0)), set.iter().next());
+}
+
+#[test]
+fn field_struct_debug() {
+    assert_eq!(
+        "FieldStruct { value: 1, marker: PhantomData<tests::DebugOnly> }",
+        format!("{:?}", FieldStruct::<DebugOnly>::new(1))
+    );
+}
+
+#[allow(dropping_copy_types)]
 #[test]
 fn field_struct_copy() {
     let value = FieldStruct::<DebugOnly>::default();
@@ -88,19 +158,32 @@ fn field_struct_copy() {
     drop(copy);
 }
 
-#[derive(Debug, ForceDefault, ForceClone, ForceCopy, ForceEq, ForcePartialEq, ForceHash)]
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceCopy,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
 pub struct UnitStruct;
 
+#[allow(clippy::default_constructed_unit_structs)]
 #[test]
 fn unit_struct_default() {
     UnitStruct::default();
 }
 
+#[allow(clippy::default_constructed_unit_structs)]
 #[test]
 fn unit_struct_eq() {
     assert_eq!(UnitStruct::default(), UnitStruct::default());
 }
 
+#[allow(clippy::default_constructed_unit_structs)]
 #[test]
 fn unit_struct_hash() {
     let mut set = std::collections::HashSet::<UnitStruct>::default();
@@ -108,6 +191,25 @@ fn unit_struct_hash() {
     assert!(!set.insert(UnitStruct::default()));
 }
 
+#[test]
+fn unit_struct_partial_ord() {
+    assert_eq!(
+        Some(std::cmp::Ordering::Equal),
+        UnitStruct.partial_cmp(&UnitStruct)
+    );
+}
+
+#[test]
+fn unit_struct_ord() {
+    assert_eq!(std::cmp::Ordering::Equal, UnitStruct.cmp(&UnitStruct));
+}
+
+#[test]
+fn unit_struct_debug() {
+    assert_eq!("UnitStruct", format!("{:?}", UnitStruct));
+}
+
+#[allow(dropping_copy_types, clippy::default_constructed_unit_structs)]
 #[test]
 fn unit_struct_copy() {
     let value = UnitStruct::default();
@@ -116,7 +218,17 @@ fn unit_struct_copy() {
     drop(copy);
 }
 
-#[derive(Debug, ForceDefault, ForceCopy, ForceClone, ForceEq, ForcePartialEq, ForceHash)]
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceCopy,
+    ForceClone,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
 pub enum NamedEnum<T> {
     First { a: u32 },
     Second { value: PhantomData<T> },
@@ -133,12 +245,14 @@ fn named_enum_default() {
     NamedEnum::<DebugOnly>::default();
 }
 
+#[allow(clippy::clone_on_copy)]
 #[test]
 fn named_enum_clone() {
     let named_enum = NamedEnum::<DebugOnly>::first(1);
     let _clone = named_enum.clone();
 }
 
+#[allow(dropping_copy_types)]
 #[test]
 fn named_enum_copy() {
     let named_enum = NamedEnum::<DebugOnly>::first(1);
@@ -185,7 +299,48 @@ fn named_enum_hash() {
     );
 }
 
-#[derive(Debug, ForceDefault, ForceCopy, ForceClone, ForceEq, ForcePartialEq, ForceHash)]
+#[test]
+fn named_enum_partial_ord() {
+    assert!(NamedEnum::<DebugOnly>::First { a: 0 } < NamedEnum::First { a: 1 });
+    assert!(NamedEnum::<DebugOnly>::First { a: 1 } < NamedEnum::Second { value: PhantomData });
+    assert!(NamedEnum::<DebugOnly>::Second { value: PhantomData } > NamedEnum::First { a: 0 });
+}
+
+#[test]
+fn named_enum_ord() {
+    assert_eq!(
+        std::cmp::Ordering::Less,
+        NamedEnum::<DebugOnly>::First { a: 0 }.cmp(&NamedEnum::First { a: 1 })
+    );
+    assert_eq!(
+        std::cmp::Ordering::Greater,
+        NamedEnum::<DebugOnly>::Second { value: PhantomData }.cmp(&NamedEnum::First { a: 1 })
+    );
+    assert_eq!(
+        std::cmp::Ordering::Equal,
+        NamedEnum::<DebugOnly>::First { a: 1 }.cmp(&NamedEnum::First { a: 1 })
+    );
+}
+
+#[test]
+fn named_enum_debug() {
+    assert_eq!(
+        "First { a: 1 }",
+        format!("{:?}", NamedEnum::<DebugOnly>::first(1))
+    );
+}
+
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceCopy,
+    ForceClone,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
 pub enum UnnamedEnum<T> {
     First(u32, u64),
     Second(PhantomData<T>),
@@ -196,12 +351,14 @@ fn unnamed_enum_default() {
     UnnamedEnum::<DebugOnly>::default();
 }
 
+#[allow(clippy::clone_on_copy)]
 #[test]
 fn unnamed_enum_clone() {
     let unnamed_enum = UnnamedEnum::<DebugOnly>::First(1, 2);
     let _clone = unnamed_enum.clone();
 }
 
+#[allow(dropping_copy_types)]
 #[test]
 fn unnamed_enum_copy() {
     let unnamed_enum = UnnamedEnum::<DebugOnly>::First(1, 2);
@@ -260,7 +417,49 @@ fn unnamed_enum_hash() {
     );
 }
 
-#[derive(Debug, ForceDefault, ForceCopy, ForceClone, ForcePartialEq, ForceHash)]
+#[test]
+fn unnamed_enum_partial_ord() {
+    assert!(UnnamedEnum::<DebugOnly>::First(1, 2) < UnnamedEnum::First(2, 1));
+    assert!(UnnamedEnum::<DebugOnly>::First(1, 1) < UnnamedEnum::First(1, 2));
+    assert!(UnnamedEnum::<DebugOnly>::First(2, 2) < UnnamedEnum::Second(PhantomData));
+    assert_eq!(
+        Some(std::cmp::Ordering::Equal),
+        UnnamedEnum::<DebugOnly>::First(1, 2).partial_cmp(&UnnamedEnum::First(1, 2))
+    );
+}
+
+#[test]
+fn unnamed_enum_ord() {
+    let mut values = vec![
+        UnnamedEnum::<DebugOnly>::Second(PhantomData),
+        UnnamedEnum::First(2, 1),
+        UnnamedEnum::First(1, 2),
+        UnnamedEnum::First(1, 1),
+    ];
+    values.sort();
+
+    assert_eq!(
+        vec![
+            UnnamedEnum::First(1, 1),
+            UnnamedEnum::First(1, 2),
+            UnnamedEnum::First(2, 1),
+            UnnamedEnum::Second(PhantomData),
+        ],
+        values
+    );
+}
+
+#[test]
+fn unnamed_enum_debug() {
+    assert_eq!(
+        "First(1, 2)",
+        format!("{:?}", UnnamedEnum::<DebugOnly>::First(1, 2))
+    );
+}
+
+#[derive(
+    Debug, ForceDefault, ForceCopy, ForceClone, ForcePartialEq, ForcePartialOrd, ForceHash,
+)]
 pub enum UnitEnum {
     First,
     Second,
@@ -271,12 +470,14 @@ fn unit_enum_default() {
     UnitEnum::default();
 }
 
+#[allow(clippy::clone_on_copy)]
 #[test]
 fn unit_enum_clone() {
     let unit_enum = UnitEnum::First;
     let _clone = unit_enum.clone();
 }
 
+#[allow(dropping_copy_types)]
 #[test]
 fn unit_enum_copy() {
     let unit_enum = UnitEnum::First;
@@ -301,6 +502,940 @@ fn unit_enum_hash() {
     assert_eq!(get_hash(UnitEnum::Second, s), get_hash(UnitEnum::Second, s));
 }
 
+#[test]
+fn unit_enum_partial_ord() {
+    assert!(UnitEnum::First < UnitEnum::Second);
+    assert!(UnitEnum::Second > UnitEnum::First);
+    assert!(UnitEnum::First <= UnitEnum::First);
+}
+
+#[test]
+fn unit_enum_debug() {
+    assert_eq!("Second", format!("{:?}", UnitEnum::Second));
+}
+
+#[derive(ForceDebug, ForceClone, ForcePartialEq)]
+pub enum CloneEnum<T> {
+    Named {
+        name: String,
+        values: Vec<u32>,
+        marker: PhantomData<T>,
+    },
+    Unnamed(String, u32),
+    Unit,
+}
+
+#[test]
+fn clone_enum_clone() {
+    let named = CloneEnum::<DebugOnly>::Named {
+        name: "name".to_string(),
+        values: vec![1, 2, 3],
+        marker: PhantomData,
+    };
+    assert_eq!(named, named.clone());
+
+    let unnamed = CloneEnum::<DebugOnly>::Unnamed("name".to_string(), 1);
+    assert_eq!(unnamed, unnamed.clone());
+}
+
+#[test]
+fn clone_enum_clone_from() {
+    let mut value = CloneEnum::<DebugOnly>::Named {
+        name: String::new(),
+        values: Vec::with_capacity(100),
+        marker: PhantomData,
+    };
+    let source = CloneEnum::Named {
+        name: "name".to_string(),
+        values: vec![1, 2, 3],
+        marker: PhantomData,
+    };
+
+    value.clone_from(&source);
+    assert_eq!(source, value);
+    match &value {
+        CloneEnum::Named { values, .. } => assert!(values.capacity() >= 100),
+        _ => unreachable!(),
+    }
+
+    value.clone_from(&CloneEnum::Unit);
+    assert_eq!(CloneEnum::Unit, value);
+
+    value.clone_from(&CloneEnum::Unnamed("name".to_string(), 1));
+    assert_eq!(CloneEnum::Unnamed("name".to_string(), 1), value);
+}
+
+#[derive(ForceDebug, ForceDefault, ForceClone, ForcePartialEq)]
+#[force(bound = "U: std::fmt::Debug")]
+#[force(
+    default(bound = "U: Default"),
+    clone(bound = "U: Clone"),
+    partial_eq(bound = "U: PartialEq")
+)]
+pub struct BoundStruct<T, U> {
+    value: U,
+    marker: PhantomData<T>,
+}
+
+#[test]
+fn bound_struct_default() {
+    assert_eq!(
+        String::new(),
+        BoundStruct::<DebugOnly, String>::default().value
+    );
+}
+
+#[test]
+fn bound_struct_clone() {
+    let value = BoundStruct::<DebugOnly, String> {
+        value: "value".to_string(),
+        marker: PhantomData,
+    };
+    assert_eq!(value, value.clone());
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceEq, ForcePartialOrd, ForceOrd, ForceHash)]
+#[force(check_consistency)]
+pub struct SkipStruct<T> {
+    id: u32,
+    #[force(skip)]
+    pub cache: Option<u64>,
+    marker: PhantomData<T>,
+}
+
+#[cfg(test)]
+impl<T> SkipStruct<T> {
+    fn new(id: u32, cache: Option<u64>) -> Self {
+        Self {
+            id,
+            cache,
+            marker: PhantomData,
+        }
+    }
+}
+
+#[test]
+fn skip_struct_eq() {
+    assert_eq!(
+        SkipStruct::<DebugOnly>::new(1, None),
+        SkipStruct::new(1, Some(2))
+    );
+    assert_ne!(
+        SkipStruct::<DebugOnly>::new(1, None),
+        SkipStruct::new(2, None)
+    );
+    assert_eq!(
+        std::cmp::Ordering::Equal,
+        SkipStruct::<DebugOnly>::new(1, None).cmp(&SkipStruct::new(1, Some(2)))
+    );
+}
+
+#[test]
+fn skip_struct_hash() {
+    let s = &RandomState::new();
+
+    assert_eq!(
+        get_hash(SkipStruct::<DebugOnly>::new(1, None), s),
+        get_hash(SkipStruct::<DebugOnly>::new(1, Some(2)), s)
+    );
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForcePartialOrd, ForceHash)]
+pub struct PartialSkipStruct {
+    id: u32,
+    #[force(skip(hash, partial_ord))]
+    version: u32,
+}
+
+#[test]
+fn partial_skip_struct() {
+    let s = &RandomState::new();
+    let (a, b) = (
+        PartialSkipStruct { id: 1, version: 1 },
+        PartialSkipStruct { id: 1, version: 2 },
+    );
+
+    assert_ne!(a, b);
+    assert_eq!(Some(std::cmp::Ordering::Equal), a.partial_cmp(&b));
+    assert_eq!(get_hash(a, s), get_hash(b, s));
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceEq, ForceHash)]
+pub enum SkipEnum {
+    Named {
+        id: u32,
+        #[force(skip)]
+        cache: u64,
+    },
+    Unnamed(#[force(skip)] u64, u32),
+}
+
+#[test]
+fn skip_enum_eq() {
+    assert_eq!(
+        SkipEnum::Named { id: 1, cache: 2 },
+        SkipEnum::Named { id: 1, cache: 3 }
+    );
+    assert_ne!(
+        SkipEnum::Named { id: 1, cache: 2 },
+        SkipEnum::Named { id: 2, cache: 2 }
+    );
+    assert_eq!(SkipEnum::Unnamed(2, 1), SkipEnum::Unnamed(3, 1));
+    assert_ne!(SkipEnum::Unnamed(2, 1), SkipEnum::Unnamed(2, 2));
+}
+
+#[test]
+fn skip_enum_hash() {
+    let s = &RandomState::new();
+
+    assert_eq!(
+        get_hash(SkipEnum::Named { id: 1, cache: 2 }, s),
+        get_hash(SkipEnum::Named { id: 1, cache: 3 }, s)
+    );
+    assert_eq!(
+        get_hash(SkipEnum::Unnamed(2, 1), s),
+        get_hash(SkipEnum::Unnamed(3, 1), s)
+    );
+}
+
+pub fn bits_eq(lhs: &f64, rhs: &f64) -> bool {
+    lhs.to_bits() == rhs.to_bits()
+}
+
+pub fn hash_bits<H: Hasher>(value: &f64, state: &mut H) {
+    value.to_bits().hash(state);
+}
+
+pub fn ptr_eq(lhs: &*const u8, rhs: &*const u8) -> bool {
+    std::ptr::eq(*lhs, *rhs)
+}
+
+pub fn hash_ptr<H: Hasher>(ptr: &*const u8, state: &mut H) {
+    std::ptr::hash(*ptr, state);
+}
+
+#[derive(ForceDebug, ForceClone, ForceCopy, ForcePartialEq, ForceEq, ForceHash)]
+#[force(check_consistency)]
+pub struct WithStruct<T> {
+    #[force(eq_with = "crate::bits_eq", hash_with = "crate::hash_bits")]
+    value: f64,
+    marker: PhantomData<T>,
+}
+
+#[test]
+fn with_struct() {
+    let s = &RandomState::new();
+    let nan = WithStruct::<DebugOnly> {
+        value: f64::NAN,
+        marker: PhantomData,
+    };
+    let zero = WithStruct::<DebugOnly> {
+        value: 0.0,
+        marker: PhantomData,
+    };
+
+    assert_eq!(nan, nan);
+    assert_ne!(
+        zero,
+        WithStruct {
+            value: -0.0,
+            ..zero
+        }
+    );
+    assert_eq!(get_hash(nan, s), get_hash(nan, s));
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceHash)]
+pub enum WithEnum {
+    Float(#[force(eq_with = "bits_eq", hash_with = "hash_bits")] f64),
+    Ptr {
+        #[force(eq_with = "ptr_eq", hash_with = "hash_ptr")]
+        ptr: *const u8,
+    },
+}
+
+#[test]
+fn with_enum() {
+    let s = &RandomState::new();
+    let bytes = [0u8; 2];
+
+    assert_eq!(WithEnum::Float(f64::NAN), WithEnum::Float(f64::NAN));
+    assert_eq!(
+        get_hash(WithEnum::Float(f64::NAN), s),
+        get_hash(WithEnum::Float(f64::NAN), s)
+    );
+    assert_eq!(
+        WithEnum::Ptr { ptr: &bytes[0] },
+        WithEnum::Ptr { ptr: &bytes[0] }
+    );
+    assert_ne!(
+        WithEnum::Ptr { ptr: &bytes[0] },
+        WithEnum::Ptr { ptr: &bytes[1] }
+    );
+    assert_eq!(
+        get_hash(WithEnum::Ptr { ptr: &bytes[0] }, s),
+        get_hash(WithEnum::Ptr { ptr: &bytes[0] }, s)
+    );
+}
+
+#[derive(ForceDefault)]
+pub struct DefaultStruct<T> {
+    #[force(default = 16)]
+    pub capacity: usize,
+    #[force(default = "unnamed".into())]
+    pub name: String,
+    #[force(default)]
+    pub count: u32,
+    marker: PhantomData<T>,
+}
+
+#[test]
+fn default_struct_default() {
+    let value = DefaultStruct::<DebugOnly>::default();
+    assert_eq!(16, value.capacity);
+    assert_eq!("unnamed", value.name);
+    assert_eq!(0, value.count);
+}
+
+#[derive(ForceDefault)]
+pub struct DefaultTupleStruct<T>(#[force(default = 1.5)] pub f64, pub u8, PhantomData<T>);
+
+#[test]
+fn default_tuple_struct_default() {
+    let value = DefaultTupleStruct::<DebugOnly>::default();
+    assert_eq!(1.5, value.0);
+    assert_eq!(0, value.1);
+}
+
+#[derive(ForceDebug, ForceDefault, ForcePartialEq)]
+pub enum DefaultEnum {
+    First(#[force(default = -1)] i32, u32),
+    Second,
+}
+
+#[test]
+fn default_enum_default() {
+    assert_eq!(DefaultEnum::First(-1, 0), DefaultEnum::default());
+}
+
+#[derive(ForceDebug, ForceDefault, ForcePartialEq)]
+pub enum DefaultVariantEnum<T> {
+    First(PhantomData<T>),
+    #[default]
+    Second {
+        #[force(default = 2)]
+        value: u32,
+    },
+}
+
+#[test]
+fn default_variant_enum_default() {
+    assert_eq!(
+        DefaultVariantEnum::<DebugOnly>::Second { value: 2 },
+        DefaultVariantEnum::default()
+    );
+}
+
+#[derive(ForceDebug, ForceDefault, ForcePartialEq)]
+pub enum ForceDefaultVariantEnum {
+    First,
+    Second,
+    #[force(default)]
+    Third,
+}
+
+#[test]
+fn force_default_variant_enum_default() {
+    assert_eq!(
+        ForceDefaultVariantEnum::Third,
+        ForceDefaultVariantEnum::default()
+    );
+}
+
+#[derive(ForceClone, ForceCopy)]
+#[repr(C)]
+pub union Handle<T> {
+    raw: u32,
+    bytes: [u8; 4],
+    marker: PhantomData<T>,
+}
+
+#[allow(clippy::clone_on_copy)]
+#[test]
+fn union_clone() {
+    let handle = Handle::<DebugOnly> { raw: 0x0102_0304 };
+    let clone = handle.clone();
+    assert_eq!(0x0102_0304, unsafe { clone.raw });
+    assert_eq!(unsafe { handle.bytes }, unsafe { clone.bytes });
+}
+
+#[allow(dropping_copy_types)]
+#[test]
+fn union_copy() {
+    let handle = Handle::<DebugOnly> { raw: 1 };
+    let copy = handle;
+    drop(handle);
+    drop(copy);
+}
+
+#[force_derive(id)]
+pub struct AttrId<T>(u32, PhantomData<T>);
+
+#[allow(clippy::clone_on_copy)]
+#[test]
+fn attr_id() {
+    let s = &RandomState::new();
+    let id = AttrId::<DebugOnly>(1, PhantomData);
+    let copy = id;
+
+    assert!(id == copy);
+    assert!(AttrId::<DebugOnly>::default() != id);
+    assert_eq!(get_hash(id, s), get_hash(copy.clone(), s));
+}
+
+#[force_derive(Debug, Default, Clone, PartialEq)]
+pub enum AttrEnum<T> {
+    First(#[force(skip)] u32, PhantomData<T>),
+    #[default]
+    Second {
+        #[force(default = 2)]
+        value: u32,
+    },
+}
+
+#[test]
+fn attr_enum() {
+    assert_eq!(
+        AttrEnum::<DebugOnly>::Second { value: 2 },
+        AttrEnum::default()
+    );
+    assert_eq!(
+        AttrEnum::<DebugOnly>::First(1, PhantomData),
+        AttrEnum::First(2, PhantomData).clone()
+    );
+}
+
+#[force_derive(Clone)]
+#[derive(ForcePartialEq)]
+pub struct Entity {
+    pub id: u32,
+    #[force(skip)]
+    pub cache: u32,
+}
+
+#[test]
+fn attr_with_later_derive() {
+    let entity = Entity { id: 1, cache: 2 };
+
+    assert!(entity == Entity { id: 1, cache: 3 });
+    assert!(entity != Entity { id: 2, cache: 2 });
+    assert_eq!(2, entity.clone().cache);
+}
+
+#[force_derive(PartialEq)]
+#[force_derive(Hash)]
+pub struct StackedEntity {
+    pub id: u32,
+    #[force(skip)]
+    pub cache: u32,
+}
+
+#[test]
+fn attr_with_later_force_derive() {
+    let s = &RandomState::new();
+    let a = StackedEntity { id: 1, cache: 2 };
+    let b = StackedEntity { id: 1, cache: 3 };
+
+    assert!(a == b);
+    assert_eq!(get_hash(&a, s), get_hash(&b, s));
+}
+
+#[derive(ForceDeref, ForceDerefMut)]
+pub struct Ids<T>(Vec<u32>, PhantomData<T>);
+
+#[test]
+fn ids_deref() {
+    let mut ids = Ids::<DebugOnly>(vec![1, 2], PhantomData);
+    ids.push(3);
+
+    assert_eq!(&[1, 2, 3], ids.as_slice());
+    assert_eq!(3, ids.len());
+}
+
+#[derive(ForceDeref, ForceDerefMut)]
+pub struct Labelled<T> {
+    pub label: &'static str,
+    #[force(deref)]
+    pub value: T,
+}
+
+#[test]
+fn labelled_deref() {
+    let mut labelled = Labelled {
+        label: "count",
+        value: 1u32,
+    };
+    *labelled += 1;
+
+    assert_eq!(2, *labelled);
+    assert_eq!("count", labelled.label);
+}
+
+#[derive(ForceDebug, ForceClone, ForceCopy, ForcePartialEq, ForceFrom, ForceInto)]
+pub struct FromId<T>(u32, PhantomData<T>);
+
+#[test]
+fn from_id() {
+    let id = FromId::<DebugOnly>::from(1);
+
+    assert_eq!(FromId(1, PhantomData), id);
+    assert_eq!(1u32, id.into());
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceFrom, ForceInto)]
+pub struct Meters<T> {
+    unit: PhantomData<T>,
+    pub value: f64,
+}
+
+#[test]
+fn from_meters() {
+    let meters: Meters<DebugOnly> = 1.5.into();
+
+    assert_eq!(1.5, meters.value);
+    assert_eq!(1.5, f64::from(meters));
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceFrom)]
+pub enum Message<T> {
+    Text(String),
+    Code {
+        code: u32,
+    },
+    #[force(skip(from))]
+    Retry(u32),
+    Pair(u8, u8),
+    Empty,
+    Marker(PhantomData<T>),
+}
+
+#[test]
+fn from_message() {
+    assert_eq!(
+        Message::<DebugOnly>::Text("text".to_string()),
+        "text".to_string().into()
+    );
+    assert_eq!(Message::<DebugOnly>::Code { code: 2 }, 2.into());
+}
+
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForcePartialEq,
+    ForceEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+    ForceFrom,
+    ForceDeref,
+)]
+pub struct Pinned<T> {
+    value: u32,
+    _pin: std::marker::PhantomPinned,
+    marker: core::marker::PhantomData<fn() -> T>,
+}
+
+#[test]
+fn pinned() {
+    let s = &RandomState::new();
+    let pinned = Pinned::<DebugOnly>::from(1);
+
+    assert_eq!(1, *pinned);
+    assert_eq!(Pinned::from(0), Pinned::<DebugOnly>::default());
+    assert!(Pinned::<DebugOnly>::default() < pinned);
+    assert_eq!(
+        get_hash(Pinned::<DebugOnly>::from(1), s),
+        get_hash(pinned, s)
+    );
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceZeroed)]
+#[repr(C)]
+pub struct Position<T>([f64; 3], PhantomData<T>);
+
+#[derive(ForceDebug, ForcePartialEq, ForceZeroed)]
+#[repr(C)]
+pub struct Record<T> {
+    id: u32,
+    position: Position<T>,
+    flags: (bool, char),
+    next: *const Record<T>,
+    pin: std::marker::PhantomPinned,
+    corners: [Position<T>; 2],
+}
+
+#[cfg(test)]
+const ZEROED: Record<DebugOnly> = Record::zeroed();
+
+#[test]
+fn record_zeroed() {
+    assert_eq!(
+        Record::<DebugOnly> {
+            id: 0,
+            position: Position([0.0; 3], PhantomData),
+            flags: (false, '\0'),
+            next: std::ptr::null(),
+            pin: std::marker::PhantomPinned,
+            corners: [
+                Position([0.0; 3], PhantomData),
+                Position([0.0; 3], PhantomData)
+            ],
+        },
+        ZEROED
+    );
+}
+
+#[derive(
+    ForceDebug,
+    ForceClone,
+    ForceCopy,
+    ForcePartialEq,
+    ForceAdd,
+    ForceSub,
+    ForceMul,
+    ForceDiv,
+    ForceAddAssign,
+    ForceSubAssign,
+    ForceMulAssign,
+    ForceDivAssign,
+    ForceNeg,
+    ForceSum,
+    ForceProduct,
+)]
+pub struct Quantity<Unit>(f64, PhantomData<Unit>);
+
+#[cfg(test)]
+fn quantity(value: f64) -> Quantity<DebugOnly> {
+    Quantity(value, PhantomData)
+}
+
+#[test]
+fn quantity_ops() {
+    assert_eq!(quantity(3.0), quantity(1.0) + quantity(2.0));
+    assert_eq!(quantity(-1.0), quantity(1.0) - quantity(2.0));
+    assert_eq!(quantity(6.0), quantity(2.0) * quantity(3.0));
+    assert_eq!(quantity(2.0), quantity(6.0) / quantity(3.0));
+}
+
+#[allow(clippy::op_ref)]
+#[test]
+fn quantity_ref_ops() {
+    let (a, b) = (quantity(6.0), quantity(3.0));
+
+    assert_eq!(quantity(9.0), &a + &b);
+    assert_eq!(quantity(3.0), &a - &b);
+    assert_eq!(quantity(18.0), &a * &b);
+    assert_eq!(quantity(2.0), &a / &b);
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceAdd, ForceSub, ForceAddAssign, ForceNeg, ForceSum)]
+pub struct Vector<Unit> {
+    pub x: i32,
+    pub y: i32,
+    unit: PhantomData<Unit>,
+}
+
+#[cfg(test)]
+fn vector(x: i32, y: i32) -> Vector<DebugOnly> {
+    Vector {
+        x,
+        y,
+        unit: PhantomData,
+    }
+}
+
+#[test]
+fn vector_ops() {
+    assert_eq!(vector(4, 6), vector(1, 2) + vector(3, 4));
+    assert_eq!(vector(-2, -2), &vector(1, 2) - &vector(3, 4));
+}
+
+#[test]
+fn quantity_assign_ops() {
+    let mut value = quantity(1.0);
+
+    value += quantity(5.0);
+    assert_eq!(quantity(6.0), value);
+    value -= quantity(2.0);
+    assert_eq!(quantity(4.0), value);
+    value *= quantity(3.0);
+    assert_eq!(quantity(12.0), value);
+    value /= quantity(4.0);
+    assert_eq!(quantity(3.0), value);
+}
+
+#[test]
+fn unary_ops() {
+    assert_eq!(quantity(-1.0), -quantity(1.0));
+    assert_eq!(vector(-1, 2), -vector(1, -2));
+    assert_eq!(
+        Mask::<DebugOnly>(0xF0, PhantomData),
+        !Mask(0x0F, PhantomData)
+    );
+    assert_eq!(Unitless, -Unitless);
+}
+
+#[test]
+fn quantity_sum_product() {
+    let values = vec![quantity(1.0), quantity(2.0), quantity(3.0)];
+
+    assert_eq!(quantity(6.0), values.iter().sum());
+    assert_eq!(quantity(6.0), values.iter().product());
+    assert_eq!(quantity(6.0), values.into_iter().sum());
+    assert_eq!(quantity(0.0), std::iter::empty::<Quantity<_>>().sum());
+    assert_eq!(quantity(1.0), std::iter::empty::<&Quantity<_>>().product());
+}
+
+#[test]
+fn vector_sum() {
+    let values = vec![vector(1, 2), vector(3, 4)];
+
+    assert_eq!(vector(4, 6), values.iter().sum());
+    assert_eq!(vector(4, 6), values.into_iter().sum());
+}
+
+#[test]
+fn vector_assign_ops() {
+    let mut value = vector(1, 2);
+    value += vector(3, 4);
+    assert_eq!(vector(4, 6), value);
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceNot)]
+pub struct Mask<T>(u8, PhantomData<T>);
+
+#[derive(ForceDebug, ForcePartialEq, ForceAddAssign, ForceNeg)]
+pub struct Unitless;
+
+#[derive(ForceDebug, ForceClone, ForceCopy, ForcePartialEq, ForceDeref, ForceMul, ForceDiv)]
+#[force(
+    mul(output = "Area<U>"),
+    mul(rhs = "f64"),
+    div(rhs = "Time<U>", output = "Speed<U>")
+)]
+pub struct Length<U>(f64, PhantomData<U>);
+
+#[derive(ForceDebug, ForcePartialEq, ForceFrom)]
+pub struct Area<U>(f64, PhantomData<U>);
+
+#[derive(ForceDebug, ForcePartialEq, ForceDeref, ForceFrom)]
+pub struct Time<U> {
+    seconds: f64,
+    unit: PhantomData<U>,
+}
+
+#[derive(ForceDebug, ForcePartialEq, ForceFrom)]
+pub struct Speed<U>(f64, PhantomData<U>);
+
+#[cfg(test)]
+fn length(value: f64) -> Length<DebugOnly> {
+    Length(value, PhantomData)
+}
+
+#[allow(clippy::op_ref)]
+#[test]
+fn dimensional_ops() {
+    let time = Time::<DebugOnly>::from(2.0);
+
+    assert_eq!(Area::from(6.0), length(2.0) * length(3.0));
+    assert_eq!(Area::from(6.0), &length(2.0) * &length(3.0));
+    assert_eq!(Speed::from(3.0), length(6.0) / time);
+    assert_eq!(Speed::from(3.0), &length(6.0) / &Time::from(2.0));
+    assert_eq!(length(3.0), length(6.0) / length(2.0));
+    assert_eq!(length(6.0), length(2.0) * 3.0);
+    assert_eq!(length(6.0), &length(2.0) * &3.0);
+}
+
+#[derive(ForceDisplay)]
+#[force(display = "{0} kg")]
+pub struct Mass<U>(f64, PhantomData<U>);
+
+#[derive(ForceDisplay)]
+#[force(display = "{type}: ({x}, {y:.1}) {{{}}}")]
+pub struct Point<U> {
+    x: i32,
+    y: f64,
+    r#type: &'static str,
+    unit: PhantomData<U>,
+}
+
+#[derive(ForceDisplay)]
+pub struct Origin;
+
+#[derive(ForceDisplay)]
+pub enum Shape<U> {
+    #[force(display = "circle r={radius}")]
+    Circle {
+        radius: f64,
+        unit: PhantomData<U>,
+    },
+    #[force(display = "{} x {}, area {0}*{1}")]
+    Rect(f64, f64),
+    #[force(display = "marker")]
+    Marker(PhantomData<U>),
+    Empty,
+}
+
+#[test]
+fn display() {
+    assert_eq!("1.5 kg", Mass::<DebugOnly>(1.5, PhantomData).to_string());
+    assert_eq!(
+        "point: (1, 2.0) {1}",
+        Point::<DebugOnly> {
+            x: 1,
+            y: 2.0,
+            r#type: "point",
+            unit: PhantomData,
+        }
+        .to_string()
+    );
+    assert_eq!("Origin", Origin.to_string());
+}
+
+#[test]
+fn display_enum() {
+    assert_eq!(
+        "circle r=1",
+        Shape::<DebugOnly>::Circle {
+            radius: 1.0,
+            unit: PhantomData
+        }
+        .to_string()
+    );
+    assert_eq!(
+        "2 x 3, area 2*3",
+        Shape::<DebugOnly>::Rect(2.0, 3.0).to_string()
+    );
+    assert_eq!(
+        "marker",
+        Shape::<DebugOnly>::Marker(PhantomData).to_string()
+    );
+    assert_eq!("Empty", Shape::<DebugOnly>::Empty.to_string());
+}
+
+#[derive(ForceDisplay)]
+#[force(display = "{:.*} [{2:>3$}] [{label:<width$}]")]
+pub struct Padded {
+    width: usize,
+    value: f64,
+    label: &'static str,
+    padding: usize,
+}
+
+#[test]
+fn display_width_and_precision() {
+    let padded = Padded {
+        width: 3,
+        value: 1.23456,
+        label: "ab",
+        padding: 4,
+    };
+
+    assert_eq!("1.235 [  ab] [ab ]", padded.to_string());
+}
+
+#[allow(dead_code, non_camel_case_types)]
+mod shadowed {
+    use force_derive::*;
+
+    pub trait Default {}
+    pub trait Clone {}
+    pub trait Copy {}
+    pub trait PartialEq {}
+    pub trait Eq {}
+    pub trait PartialOrd {}
+    pub trait Ord {}
+    pub trait Hash {}
+    pub trait Hasher {}
+    pub trait Debug {}
+    pub struct Option;
+    pub struct Some;
+    pub struct None;
+    pub struct Ordering;
+    pub struct PhantomData;
+    pub type bool = u8;
+    pub type usize = u8;
+    pub mod core {}
+    pub mod std {}
+
+    #[derive(
+        ForceDebug,
+        ForceDefault,
+        ForceClone,
+        ForceCopy,
+        ForceEq,
+        ForcePartialEq,
+        ForcePartialOrd,
+        ForceOrd,
+        ForceHash,
+    )]
+    pub struct Id<H>(pub u32, pub ::core::marker::PhantomData<H>);
+
+    #[derive(
+        ForceDebug,
+        ForceDefault,
+        ForceClone,
+        ForceCopy,
+        ForceEq,
+        ForcePartialEq,
+        ForcePartialOrd,
+        ForceOrd,
+        ForceHash,
+        ForceDisplay,
+    )]
+    pub enum Entry<H> {
+        #[force(display = "{f} {state} {rhs}")]
+        Named {
+            f: u32,
+            state: u32,
+            rhs: u32,
+        },
+        #[force(display = "{0}")]
+        Unnamed(u32, ::core::marker::PhantomData<H>),
+        Unit,
+    }
+}
+
+#[allow(clippy::clone_on_copy)]
+#[test]
+fn shadowed_struct() {
+    let s = &RandomState::new();
+    let id = shadowed::Id::<DebugOnly>(1, PhantomData);
+
+    assert_eq!(id, id.clone());
+    assert!(id > shadowed::Id::default());
+    assert_eq!(get_hash(id, s), get_hash(id.clone(), s));
+    assert_eq!("Id(1, PhantomData<tests::DebugOnly>)", format!("{:?}", id));
+}
+
+#[allow(clippy::clone_on_copy)]
+#[test]
+fn shadowed_enum() {
+    let s = &RandomState::new();
+    let entry = shadowed::Entry::<DebugOnly>::Named {
+        f: 1,
+        state: 2,
+        rhs: 3,
+    };
+
+    assert_eq!(entry, entry.clone());
+    assert!(entry < shadowed::Entry::Unit);
+    assert_eq!(get_hash(entry, s), get_hash(entry.clone(), s));
+    assert_eq!("Named { f: 1, state: 2, rhs: 3 }", format!("{:?}", entry));
+    assert_eq!("1 2 3", entry.to_string());
+}
+
+#[allow(clippy::manual_hash_one)]
 #[cfg(test)]
 fn get_hash<H: Hash>(value: H, s: &RandomState) -> u64 {
     let mut hasher = s.build_hasher();
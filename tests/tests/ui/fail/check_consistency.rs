@@ -0,0 +1,38 @@
+use force_derive::*;
+
+#[derive(ForcePartialEq, ForceHash)]
+#[force(check_consistency)]
+struct Entry {
+    id: u32,
+    #[force(skip(partial_eq))]
+    version: u32,
+    #[force(skip(hash))]
+    cache: u32,
+}
+
+#[derive(ForcePartialEq, ForcePartialOrd)]
+#[force(check_consistency)]
+enum Value {
+    Float(#[force(skip(partial_ord))] f64),
+    Int(#[force(skip)] i64),
+}
+
+#[derive(ForcePartialEq, ForceHash)]
+#[force(check_consistency)]
+struct Float {
+    #[force(eq_with = "f64_eq")]
+    value: f64,
+}
+
+#[derive(ForcePartialEq, ForcePartialOrd, ForceOrd)]
+#[force(check_consistency)]
+struct Bits {
+    #[force(eq_with = "f64_eq")]
+    value: f64,
+}
+
+fn f64_eq(lhs: &f64, rhs: &f64) -> bool {
+    lhs.to_bits() == rhs.to_bits()
+}
+
+fn main() {}
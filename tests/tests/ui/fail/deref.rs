@@ -0,0 +1,19 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(ForceDeref)]
+struct Ambiguous<T> {
+    id: u32,
+    generation: u32,
+    marker: PhantomData<T>,
+}
+
+#[derive(ForceDerefMut)]
+struct Marker<T>(PhantomData<T>);
+
+#[derive(ForceDeref)]
+enum Value {
+    Int(i64),
+}
+
+fn main() {}
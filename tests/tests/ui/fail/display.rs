@@ -0,0 +1,36 @@
+use force_derive::*;
+
+#[derive(ForceDisplay)]
+#[force(display = "{value} {unit}")]
+struct Named {
+    value: f64,
+}
+
+#[derive(ForceDisplay)]
+#[force(display = "{0} / {2}")]
+struct Tuple(f64, f64);
+
+#[derive(ForceDisplay)]
+struct Missing(f64);
+
+#[derive(ForceDisplay)]
+#[force(display = "{0}")]
+enum Container {
+    Value(f64),
+}
+
+#[derive(ForceDisplay)]
+enum Variant {
+    #[force(display = "{count} items")]
+    Count(usize),
+}
+
+#[derive(ForceDisplay)]
+#[force(display = "{} / {} / {}")]
+struct Implicit(f64, f64);
+
+#[derive(ForceDisplay)]
+#[force(display = "{0:>width$}")]
+struct Width(f64);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use force_derive::*;
+
+#[derive(ForceDefault)]
+enum Never {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use force_derive::*;
+
+#[force_derive(Clone, Bogus)]
+struct Unknown(u32);
+
+#[force_derive(id, Clone)]
+struct Duplicate(u32);
+
+fn main() {}
@@ -0,0 +1,21 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(ForceFrom)]
+struct Pair<T>(u32, u32, PhantomData<T>);
+
+#[derive(ForceInto)]
+struct Marker<T>(PhantomData<T>);
+
+#[derive(ForceFrom)]
+enum Value {
+    Int(i64),
+    Count { count: i64 },
+}
+
+#[derive(ForceInto)]
+enum Id {
+    Int(u32),
+}
+
+fn main() {}
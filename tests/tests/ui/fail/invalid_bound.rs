@@ -0,0 +1,11 @@
+use force_derive::*;
+
+#[derive(ForceClone)]
+#[force(bound = "T Clone")]
+struct Parsed<T>(T);
+
+#[derive(ForceClone)]
+#[force(bound = 5)]
+struct Literal<T>(T);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use force_derive::*;
+
+#[derive(ForceDefault)]
+enum State {
+    #[default]
+    Idle,
+    #[force(default)]
+    Running,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(ForceAdd)]
+#[force(add(rhs = "Offset<U>"))]
+struct Position<U>(f64, PhantomData<U>);
+
+struct Offset<U>(f64, PhantomData<U>);
+
+#[derive(ForceMul)]
+#[force(mul(output = "Area"))]
+struct Size {
+    width: f64,
+    height: f64,
+}
+
+struct Area(f64);
+
+#[derive(ForceMul)]
+#[force(mul(rhs = "Scale", output = "Volume"))]
+struct Depth(f64);
+
+struct Scale(f64);
+
+struct Volume(f64);
+
+fn main() {}
@@ -0,0 +1,21 @@
+use force_derive::*;
+
+#[derive(ForceAdd)]
+enum Value {
+    Int(i64),
+}
+
+#[derive(ForceMul)]
+struct Label(String);
+
+#[derive(ForceNeg)]
+union Bits {
+    int: u32,
+}
+
+#[derive(ForceSum)]
+enum Total {
+    Int(i64),
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use force_derive::*;
+
+#[derive(ForceClone, ForceCopy, ForcePartialEq)]
+union Bits {
+    int: u32,
+    float: f32,
+}
+
+#[derive(ForceClone)]
+union Droppable {
+    value: std::mem::ManuallyDrop<String>,
+}
+
+fn main() {}
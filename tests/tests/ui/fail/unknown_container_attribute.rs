@@ -0,0 +1,11 @@
+use force_derive::*;
+
+#[derive(ForceClone)]
+#[force(skip)]
+struct Id(u32);
+
+#[derive(ForceClone)]
+#[force(clone(bond = "T: Clone"))]
+struct Value<T>(T);
+
+fn main() {}
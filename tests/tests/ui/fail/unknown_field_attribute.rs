@@ -0,0 +1,44 @@
+use force_derive::*;
+
+#[derive(ForcePartialEq)]
+struct Id {
+    #[force(skipped)]
+    value: u32,
+}
+
+#[derive(ForceHash)]
+enum Entry {
+    Value(#[force(skip = true)] u32),
+}
+
+#[derive(ForceHash)]
+struct Value {
+    #[force(skip(debug))]
+    value: u32,
+}
+
+#[derive(ForceClone, ForceCopy, ForceDebug)]
+struct Plain {
+    #[force(skpi)]
+    x: u32,
+}
+
+#[derive(ForceClone, ForceEq)]
+enum En {
+    A(#[force(bogus = 1)] u32),
+}
+
+#[derive(ForceDisplay)]
+#[force(display = "{0}")]
+struct Shown(#[force(hidden)] u32);
+
+#[derive(ForceAdd)]
+struct Meters(#[force(unit = "m")] f64);
+
+#[derive(ForceNeg)]
+struct Seconds(#[force(unti)] f64);
+
+#[derive(ForceZeroed)]
+struct Grams(#[force(zero)] f64);
+
+fn main() {}
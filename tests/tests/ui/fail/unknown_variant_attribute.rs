@@ -0,0 +1,22 @@
+use force_derive::*;
+
+#[derive(ForceClone)]
+enum State {
+    #[force(defualt)]
+    Idle,
+    Busy,
+}
+
+#[derive(ForcePartialEq, ForceHash)]
+enum Entry {
+    #[force(bogus)]
+    Value(u32),
+}
+
+#[derive(ForceFrom)]
+enum Value {
+    #[force(skip(into))]
+    Int(i64),
+}
+
+fn main() {}
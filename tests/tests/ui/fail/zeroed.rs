@@ -0,0 +1,21 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(ForceZeroed)]
+struct Name<T> {
+    len: usize,
+    name: Label,
+    marker: PhantomData<T>,
+}
+
+struct Label(u8);
+
+#[derive(ForceZeroed)]
+struct Wrapper<T>(T);
+
+#[derive(ForceZeroed)]
+enum Value {
+    Int(i64),
+}
+
+fn main() {}
@@ -0,0 +1,69 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
+#[force(bound = "U: std::fmt::Debug")]
+#[force(default(bound = "U: Default"), clone(bound = "U: Clone"))]
+#[force(partial_eq(bound = "U: PartialEq"), eq(bound = "U: Eq"))]
+#[force(partial_ord(bound = "U: PartialOrd"), ord(bound = "U: Ord"))]
+#[force(hash(bound = "U: std::hash::Hash"))]
+struct Wrapper<T, U> {
+    value: U,
+    #[force(skip, default = 16)]
+    capacity: usize,
+    #[force(default)]
+    marker: PhantomData<T>,
+}
+
+#[derive(ForceDebug, ForceDefault, ForceEq, ForcePartialEq, ForceHash)]
+enum Cached {
+    First(u32),
+    #[force(default)]
+    Second {
+        #[force(default = 1)]
+        id: u32,
+        #[force(skip)]
+        cache: Option<u64>,
+    },
+}
+
+#[derive(ForceDefault)]
+enum Marked {
+    First,
+    #[default]
+    Second,
+}
+
+struct Marker;
+
+fn main() {
+    let wrapper = Wrapper::<Marker, String>::default();
+    assert_eq!(16, wrapper.capacity);
+    assert_eq!(
+        wrapper,
+        Wrapper {
+            capacity: 0,
+            ..wrapper.clone()
+        }
+    );
+
+    assert_eq!(
+        Cached::Second {
+            id: 1,
+            cache: Some(2)
+        },
+        Cached::default()
+    );
+    assert_ne!(Cached::First(1), Cached::default());
+
+    assert!(matches!(Marked::default(), Marked::Second));
+}
@@ -0,0 +1,8 @@
+use force_derive::*;
+
+#[derive(
+    ForceDebug, ForceClone, ForceCopy, ForceEq, ForcePartialEq, ForcePartialOrd, ForceOrd, ForceHash,
+)]
+enum Never {}
+
+fn main() {}
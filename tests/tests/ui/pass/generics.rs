@@ -0,0 +1,69 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(
+    ForceDebug, ForceClone, ForceCopy, ForceEq, ForcePartialEq, ForcePartialOrd, ForceOrd, ForceHash,
+)]
+struct Slice<'a, T, const N: usize> {
+    values: &'a [u32; N],
+    marker: PhantomData<T>,
+}
+
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceCopy,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
+struct Array<T, const N: usize>(#[force(default = [0; N])] [u32; N], PhantomData<T>);
+
+#[derive(
+    ForceDebug, ForceClone, ForceCopy, ForceEq, ForcePartialEq, ForcePartialOrd, ForceOrd, ForceHash,
+)]
+enum Borrowed<'a, T: ?Sized> {
+    Value(&'a str, PhantomData<&'a T>),
+    Empty,
+}
+
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
+struct Bounded<T, U>
+where
+    U: Clone + Default + std::fmt::Debug + Ord + std::hash::Hash,
+{
+    value: U,
+    marker: PhantomData<T>,
+}
+
+struct Marker;
+
+fn main() {
+    let slice = Slice::<Marker, 2> {
+        values: &[1, 2],
+        marker: PhantomData,
+    };
+    assert_eq!(slice, slice.clone());
+
+    let array = Array::<Marker, 3>::default();
+    assert_eq!(array, Array([0; 3], PhantomData));
+
+    let borrowed = Borrowed::<str>::Value("value", PhantomData);
+    assert!(borrowed > Borrowed::Value("a", PhantomData));
+    assert!(borrowed < Borrowed::Empty);
+
+    let bounded = Bounded::<Marker, String>::default();
+    assert_eq!(bounded, bounded.clone());
+}
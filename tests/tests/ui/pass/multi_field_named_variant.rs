@@ -0,0 +1,40 @@
+use force_derive::*;
+use std::marker::PhantomData;
+
+#[derive(
+    ForceDebug,
+    ForceDefault,
+    ForceClone,
+    ForceCopy,
+    ForceEq,
+    ForcePartialEq,
+    ForcePartialOrd,
+    ForceOrd,
+    ForceHash,
+)]
+enum Entity<T> {
+    Named {
+        id: u32,
+        r#type: u8,
+        marker: PhantomData<T>,
+    },
+    Unnamed(u32, u8, PhantomData<T>),
+    Unit,
+}
+
+struct Marker;
+
+fn main() {
+    let entity = Entity::<Marker>::Named {
+        id: 1,
+        r#type: 2,
+        marker: PhantomData,
+    };
+
+    let mut clone = entity.clone();
+    clone.clone_from(&Entity::Unnamed(1, 2, PhantomData));
+
+    assert_eq!(entity, entity.clone());
+    assert!(entity < clone);
+    assert!(format!("{:?}", entity).starts_with("Named { id: 1, type: 2, marker: PhantomData<"));
+}
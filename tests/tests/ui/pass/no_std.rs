@@ -0,0 +1,46 @@
+#![no_std]
+
+extern crate std as _;
+
+mod ids {
+    use core::marker::PhantomData;
+    use force_derive::*;
+
+    #[derive(
+        ForceDebug,
+        ForceDefault,
+        ForceClone,
+        ForceCopy,
+        ForceEq,
+        ForcePartialEq,
+        ForcePartialOrd,
+        ForceOrd,
+        ForceHash,
+    )]
+    pub struct Id<T>(u32, PhantomData<T>);
+
+    #[derive(
+        ForceDebug,
+        ForceDefault,
+        ForceClone,
+        ForceCopy,
+        ForceEq,
+        ForcePartialEq,
+        ForcePartialOrd,
+        ForceOrd,
+        ForceHash,
+    )]
+    pub enum Handle<T> {
+        Named { id: u32, marker: PhantomData<T> },
+        Unnamed(u32, PhantomData<T>),
+        Unit,
+    }
+}
+
+fn main() {
+    let id = ids::Id::<u8>::default();
+    assert!(id == id.clone());
+
+    let handle = ids::Handle::<u8>::default();
+    assert!(handle <= ids::Handle::Unit);
+}